@@ -0,0 +1,168 @@
+//! Benchmarks for loading modules, run with `cargo bench`.
+//!
+//! The fixture module has 50 files by default, which can be changed with the
+//! `TFCONFIG_BENCH_FILES` environment variable.
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use std::{env, fs, path::Path};
+use tempdir::TempDir;
+use tfconfig::LoadOptions;
+
+const DEFAULT_FILE_COUNT: usize = 50;
+
+/// Returns the contents of a generated configuration file, declaring a few of each kind of block
+/// the crate interprets.
+fn fixture_file(i: usize) -> String {
+    format!(
+        r#"terraform {{
+  required_version = ">= 1.5.0"
+
+  required_providers {{
+    aws{i} = {{
+      source  = "hashicorp/aws"
+      version = ">= 5.0, < 6.0"
+    }}
+  }}
+}}
+
+provider "aws{i}" {{
+  region = "us-east-1"
+  alias  = "east"
+
+  assume_role {{
+    role_arn = "arn:aws:iam::123456789012:role/deploy"
+  }}
+}}
+
+variable "name_{i}" {{
+  type        = string
+  description = "Name of the instance"
+  default     = "web-{i}"
+}}
+
+variable "tags_{i}" {{
+  type = map(string)
+  default = {{
+    team = "platform"
+  }}
+}}
+
+resource "aws_instance" "web_{i}" {{
+  provider      = aws{i}.east
+  ami           = data.aws_ami.ubuntu_{i}.id
+  instance_type = "t3.micro"
+  tags          = merge(var.tags_{i}, {{ Name = var.name_{i} }})
+
+  dynamic "ebs_block_device" {{
+    for_each = [1, 2]
+    content {{
+      device_name = "/dev/sd${{ebs_block_device.value}}"
+    }}
+  }}
+
+  depends_on = [aws_security_group.web_{i}]
+}}
+
+resource "aws_security_group" "web_{i}" {{
+  name = "web-{i}"
+}}
+
+data "aws_ami" "ubuntu_{i}" {{
+  most_recent = true
+}}
+
+module "network_{i}" {{
+  source  = "terraform-aws-modules/vpc/aws"
+  version = "~> 5.0"
+}}
+
+output "ip_{i}" {{
+  value = aws_instance.web_{i}.public_ip
+}}
+"#
+    )
+}
+
+fn file_count() -> usize {
+    env::var("TFCONFIG_BENCH_FILES")
+        .ok()
+        .and_then(|count| count.parse().ok())
+        .unwrap_or(DEFAULT_FILE_COUNT)
+}
+
+/// Writes a module of the given number of files to a temporary directory.
+fn write_fixture(file_count: usize) -> TempDir {
+    let dir = TempDir::new("tfconfig_bench").expect("failed to create fixture directory");
+    for i in 0..file_count {
+        fs::write(dir.path().join(format!("file{i}.tf")), fixture_file(i))
+            .expect("failed to write fixture file");
+    }
+    dir
+}
+
+fn fixture_size(path: &Path) -> u64 {
+    fs::read_dir(path)
+        .expect("failed to read fixture directory")
+        .map(|entry| {
+            entry
+                .expect("failed to read fixture entry")
+                .metadata()
+                .unwrap()
+                .len()
+        })
+        .sum()
+}
+
+fn bench_load_module(c: &mut Criterion) {
+    let file_count = file_count();
+    let fixture = write_fixture(file_count);
+    let path = fixture.path();
+
+    let mut group = c.benchmark_group(format!("load_module/{file_count}_files"));
+    group.throughput(Throughput::Bytes(fixture_size(path)));
+
+    group.bench_function("load_module", |b| {
+        b.iter(|| tfconfig::load_module(path, true).unwrap())
+    });
+
+    let options = LoadOptions::new().strict(true).read_threads(4);
+    group.bench_function("load_module_with_options/read_threads_4", |b| {
+        b.iter(|| tfconfig::load_module_with_options(path, &options).unwrap())
+    });
+
+    group.bench_function("load_provider_requirements", |b| {
+        b.iter(|| tfconfig::load_provider_requirements(path, true).unwrap())
+    });
+
+    group.finish();
+}
+
+fn bench_parse(c: &mut Criterion) {
+    let file_count = file_count();
+    let contents: Vec<String> = (0..file_count).map(fixture_file).collect();
+    let bytes = contents.iter().map(|contents| contents.len() as u64).sum();
+
+    let mut group = c.benchmark_group(format!("parse/{file_count}_files"));
+    group.throughput(Throughput::Bytes(bytes));
+
+    group.bench_function("hcl_parse", |b| {
+        b.iter(|| {
+            for contents in &contents {
+                hcl::parse(contents).unwrap();
+            }
+        })
+    });
+
+    group.bench_function("parse_str", |b| {
+        b.iter(|| {
+            for contents in &contents {
+                tfconfig::parse_str(contents).unwrap();
+            }
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_load_module, bench_parse);
+criterion_main!(benches);
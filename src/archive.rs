@@ -0,0 +1,168 @@
+use crate::{
+    handle_read_file_error, is_config_file_name, is_override_file, load_file_contents,
+    provider_config::merge_inline_provider_versions, Error, LoadOptions, Module, Result,
+};
+use std::{
+    io::{self, Read, Seek},
+    path::{Component, Path, PathBuf},
+};
+
+/// Archive formats supported by [`load_module_from_archive`][load_module_from_archive].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    /// An uncompressed tar stream. Compressed tarballs should be wrapped in a decoder first.
+    Tar,
+    Zip,
+}
+
+/// Reads the archive from the given reader and attempts to interpret its root-level files as a
+/// Terraform module, without extracting it to disk.
+///
+/// Entries in subdirectories of the archive are ignored, as they belong to other modules.
+///
+/// # Arguments
+///
+/// * `reader` - Reader over the archive's contents
+/// * `format` - Format of the archive
+/// * `strict` - Whether to immediately return an error if an entry in the archive cannot be parsed
+pub fn load_module_from_archive<R: Read + Seek>(
+    reader: R,
+    format: ArchiveFormat,
+    strict: bool,
+) -> Result<Module> {
+    load_module_from_archive_with_options(reader, format, &LoadOptions::new().strict(strict))
+}
+
+/// Reads the archive from the given reader and attempts to interpret its root-level files as a
+/// Terraform module, using the given [`LoadOptions`][LoadOptions].
+///
+/// Entries larger than [`LoadOptions::max_file_size`][LoadOptions::max_file_size] are skipped as
+/// files on disk are, without reading more of their contents than the limit.
+pub fn load_module_from_archive_with_options<R: Read + Seek>(
+    reader: R,
+    format: ArchiveFormat,
+    options: &LoadOptions,
+) -> Result<Module> {
+    let mut module = Module::new(PathBuf::new());
+
+    let entries = match format {
+        ArchiveFormat::Tar => read_tar_entries(reader, options)?,
+        ArchiveFormat::Zip => read_zip_entries(reader, options)?,
+    };
+
+    let (primary, overrides): (Vec<_>, Vec<_>) = entries
+        .into_iter()
+        .partition(|(file_name, _)| !is_override_file(file_name));
+
+    for (file_name, file_contents) in primary.into_iter().chain(overrides) {
+        match file_contents {
+            Ok(file_contents) => {
+                load_file_contents(&file_name, &file_contents, options, &mut module)?
+            }
+            Err(e) => handle_read_file_error(&file_name, e, options, &mut module)?,
+        }
+    }
+
+    merge_inline_provider_versions(&mut module);
+
+    Ok(module)
+}
+
+type Entry = (PathBuf, Result<String>);
+
+fn read_tar_entries<R: Read>(reader: R, options: &LoadOptions) -> Result<Vec<Entry>> {
+    let mut archive = tar::Archive::new(reader);
+    let mut entries = vec![];
+
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+
+        let file_name = entry.path()?.into_owned();
+        if !is_root_config_file(&file_name) {
+            continue;
+        }
+
+        let size = entry.size();
+        let file_contents = read_entry(&file_name, &mut entry, size, options);
+        entries.push((file_name, file_contents));
+    }
+
+    Ok(entries)
+}
+
+fn read_zip_entries<R: Read + Seek>(reader: R, options: &LoadOptions) -> Result<Vec<Entry>> {
+    let mut archive = zip::ZipArchive::new(reader).map_err(|e| Error::Other(Box::new(e)))?;
+    let mut entries = vec![];
+
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i).map_err(|e| Error::Other(Box::new(e)))?;
+        if !entry.is_file() {
+            continue;
+        }
+
+        let file_name = match entry.enclosed_name() {
+            Some(file_name) if is_root_config_file(&file_name) => file_name,
+            _ => continue,
+        };
+
+        let size = entry.size();
+        let file_contents = read_entry(&file_name, &mut entry, size, options);
+        entries.push((file_name, file_contents));
+    }
+
+    Ok(entries)
+}
+
+/// Reads the contents of an archive entry whose header gives the given size, checking it against
+/// [`LoadOptions::max_file_size`][LoadOptions::max_file_size]. As the header may understate the
+/// size, at most one byte more than the limit is read.
+fn read_entry(
+    file_name: &Path,
+    entry: &mut impl Read,
+    size: u64,
+    options: &LoadOptions,
+) -> Result<String> {
+    let too_large = |size, limit| Error::FileTooLarge {
+        path: file_name.to_path_buf(),
+        size,
+        limit,
+    };
+
+    let mut file_contents = vec![];
+    match options.max_file_size {
+        Some(limit) if size > limit => return Err(too_large(size, limit)),
+        Some(limit) => {
+            entry
+                .take(limit.saturating_add(1))
+                .read_to_end(&mut file_contents)?;
+            let read = file_contents.len() as u64;
+            if read > limit {
+                return Err(too_large(read, limit));
+            }
+        }
+        None => {
+            entry.read_to_end(&mut file_contents)?;
+        }
+    }
+
+    String::from_utf8(file_contents)
+        .map_err(|e| Error::Io(io::Error::new(io::ErrorKind::InvalidData, e)))
+}
+
+fn is_root_config_file(path: &Path) -> bool {
+    let mut components = path
+        .components()
+        .filter(|component| !matches!(component, Component::CurDir));
+
+    let file_name = match (components.next(), components.next()) {
+        (Some(Component::Normal(file_name)), None) => file_name,
+        _ => return false,
+    };
+
+    file_name
+        .to_str()
+        .is_some_and(|file_name| is_config_file_name(file_name, false))
+}
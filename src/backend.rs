@@ -0,0 +1,47 @@
+use crate::{get_block_name, Module, Result};
+use std::{collections::HashMap, path::Path};
+
+/// A `backend` block nested in a `terraform` block.
+#[derive(Debug, Default, Clone)]
+pub struct Backend {
+    /// The backend type, such as `s3` or `local`.
+    pub type_: String,
+    /// Arguments set in the block, keyed by name.
+    pub config: HashMap<String, hcl::Expression>,
+}
+
+impl Backend {
+    pub fn new(type_: String) -> Self {
+        Self {
+            type_,
+            ..Default::default()
+        }
+    }
+
+    /// Returns whether the block sets no arguments, as is common when the backend is configured
+    /// with `-backend-config` at `terraform init` time.
+    pub fn is_partial(&self) -> bool {
+        self.config.is_empty()
+    }
+}
+
+/// Records a `backend` block. A later block, such as one in an override file, replaces any
+/// earlier one.
+pub(crate) fn handle_backend_block(
+    current_file: &Path,
+    block: &hcl::Block,
+    module: &mut Module,
+) -> Result<()> {
+    let type_ = get_block_name(current_file, block)?;
+
+    let mut backend = Backend::new(type_);
+    for attr in block.body().attributes() {
+        backend
+            .config
+            .insert(attr.key().to_string(), attr.expr().clone());
+    }
+
+    module.backend = Some(backend);
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use std::path::PathBuf;
+
+/// Severity of a [`Diagnostic`][Diagnostic].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticSeverity {
+    Error,
+    Warning,
+}
+
+/// A problem found while loading a module that did not stop it from loading.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub severity: DiagnosticSeverity,
+    pub summary: String,
+    pub detail: String,
+    pub pos: Option<SourcePos>,
+}
+
+impl Diagnostic {
+    pub fn new(severity: DiagnosticSeverity, summary: String, detail: String) -> Self {
+        Self {
+            severity,
+            summary,
+            detail,
+            pos: None,
+        }
+    }
+
+    pub fn with_pos(mut self, pos: SourcePos) -> Self {
+        self.pos = Some(pos);
+        self
+    }
+}
+
+/// A position within a file of a module.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SourcePos {
+    pub filename: PathBuf,
+    pub line: usize,
+}
+
+impl SourcePos {
+    pub fn new(filename: PathBuf, line: usize) -> Self {
+        Self { filename, line }
+    }
+}
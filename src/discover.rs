@@ -0,0 +1,52 @@
+use crate::{is_config_file_name, Result};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Directories that never contain a module of their own, such as downloaded dependencies.
+const SKIPPED_DIRS: &[&str] = &[".git", ".terraform"];
+
+/// Walks the directory tree under the given root and returns every directory that directly
+/// contains at least one `.tf` or `.tf.json` file, sorted by path.
+///
+/// Files are selected as when loading a module, so hidden and editor backup files are ignored.
+/// A directory containing only `.tf.json` files is still returned, although its files are
+/// skipped with a diagnostic when it is loaded, as the JSON syntax is not supported yet.
+///
+/// `.git` and `.terraform` directories are skipped, and symbolic links to directories are not
+/// followed.
+pub fn discover_modules(root: &Path) -> Result<Vec<PathBuf>> {
+    let mut modules = vec![];
+    discover_modules_in(root, &mut modules)?;
+    modules.sort();
+
+    Ok(modules)
+}
+
+fn discover_modules_in(dir: &Path, modules: &mut Vec<PathBuf>) -> Result<()> {
+    let mut is_module = false;
+
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        let file_name = entry.file_name();
+        let Some(file_name) = file_name.to_str() else {
+            continue;
+        };
+
+        if file_type.is_dir() {
+            if !SKIPPED_DIRS.contains(&file_name) {
+                discover_modules_in(&entry.path(), modules)?;
+            }
+        } else if is_config_file_name(file_name, false) {
+            is_module = true;
+        }
+    }
+
+    if is_module {
+        modules.push(dir.to_path_buf());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,76 @@
+use crate::{unexpected_labels, Module, Result};
+use std::{collections::HashMap, path::Path};
+
+/// An OpenTofu `encryption` block nested in a `terraform` block, configuring state and plan
+/// encryption.
+#[derive(Debug, Default, Clone)]
+pub struct Encryption {
+    /// `key_provider` blocks, in source order.
+    pub key_providers: Vec<EncryptionBlock>,
+    /// `method` blocks, in source order.
+    pub methods: Vec<EncryptionBlock>,
+    /// Blocks nested in the encryption block that are not otherwise modeled, such as `state` and
+    /// `plan`, in source order.
+    pub nested_blocks: Vec<hcl::Block>,
+}
+
+/// A `key_provider` or `method` block inside an [`Encryption`][Encryption] block.
+#[derive(Debug, Default, Clone)]
+pub struct EncryptionBlock {
+    /// The type of key provider or method, such as `pbkdf2` or `aes_gcm`.
+    pub type_: String,
+    pub name: String,
+    /// Arguments set in the block, keyed by name.
+    pub attributes: HashMap<String, hcl::Expression>,
+}
+
+impl EncryptionBlock {
+    pub fn new(type_: String, name: String) -> Self {
+        Self {
+            type_,
+            name,
+            ..Default::default()
+        }
+    }
+}
+
+/// Records an `encryption` block. A later block, such as one in an override file, replaces any
+/// earlier one.
+pub(crate) fn handle_encryption_block(
+    current_file: &Path,
+    block: &hcl::Block,
+    module: &mut Module,
+) -> Result<()> {
+    let mut encryption = Encryption::default();
+    for inner_block in block.body().blocks() {
+        match inner_block.identifier() {
+            "key_provider" => encryption
+                .key_providers
+                .push(get_encryption_block(current_file, inner_block)?),
+            "method" => encryption
+                .methods
+                .push(get_encryption_block(current_file, inner_block)?),
+            _ => encryption.nested_blocks.push(inner_block.clone()),
+        }
+    }
+
+    module.encryption = Some(encryption);
+
+    Ok(())
+}
+
+fn get_encryption_block(current_file: &Path, block: &hcl::Block) -> Result<EncryptionBlock> {
+    let [type_, name] = block.labels() else {
+        return Err(unexpected_labels(current_file, block));
+    };
+
+    let mut encryption_block =
+        EncryptionBlock::new(type_.as_str().to_string(), name.as_str().to_string());
+    for attr in block.body().attributes() {
+        encryption_block
+            .attributes
+            .insert(attr.key().to_string(), attr.expr().clone());
+    }
+
+    Ok(encryption_block)
+}
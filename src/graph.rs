@@ -0,0 +1,46 @@
+use crate::Module;
+use std::fmt::Write;
+
+impl Module {
+    /// Renders the dependencies between the module's resources and module calls as a Graphviz DOT
+    /// digraph.
+    ///
+    /// Managed resources, data resources and module calls become nodes, named by their address,
+    /// and each entry in their `depends_on` becomes an edge towards the dependency. Nodes and edges
+    /// are sorted so that the output is stable.
+    pub fn to_graphviz(&self) -> String {
+        let mut nodes: Vec<(String, &[String])> = self
+            .managed_resources
+            .iter()
+            .map(|(key, resource)| (key.clone(), resource.depends_on.as_slice()))
+            .chain(
+                self.data_resources
+                    .iter()
+                    .map(|(key, resource)| (key.clone(), resource.depends_on.as_slice())),
+            )
+            .chain(
+                self.module_calls
+                    .iter()
+                    .map(|(name, call)| (format!("module.{name}"), call.depends_on.as_slice())),
+            )
+            .collect();
+        nodes.sort();
+
+        let mut dot = String::from("digraph {\n");
+        for (node, _) in &nodes {
+            let _ = writeln!(dot, "  {};", quote(node));
+        }
+        for (node, depends_on) in &nodes {
+            for dependency in depends_on.iter() {
+                let _ = writeln!(dot, "  {} -> {};", quote(node), quote(dependency));
+            }
+        }
+        dot.push_str("}\n");
+
+        dot
+    }
+}
+
+fn quote(id: &str) -> String {
+    format!("\"{}\"", id.replace('\\', "\\\\").replace('"', "\\\""))
+}
@@ -0,0 +1,254 @@
+use crate::{effective_source, EncryptionBlock, Module, ProviderRef};
+use std::collections::HashMap;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+impl Module {
+    /// Returns a hash of the module's semantic content, for cheaply detecting whether a module
+    /// changed between loads.
+    ///
+    /// The hash covers the Terraform version constraints and other `terraform` block settings,
+    /// including the backend, `encryption` and `cloud` blocks, the provider requirements and
+    /// configurations, variables, outputs, resources, including their arguments and nested
+    /// blocks, module calls, and `moved`, `import` and `removed` blocks. Entries are sorted first,
+    /// so it does not depend on the order of files or blocks, and it is computed with FNV-1a so
+    /// that it is stable across runs, platforms and Rust versions. Diagnostics, file paths, the
+    /// lock file and variable values are not included.
+    pub fn content_hash(&self) -> u64 {
+        let mut entries: Vec<Vec<String>> = vec![];
+
+        for constraint in &self.required_core {
+            entries.push(vec!["terraform".to_string(), constraint.clone()]);
+        }
+
+        for (name, req) in &self.required_providers {
+            let mut constraints = req.version_constraints.clone();
+            constraints.sort();
+            let mut aliases: Vec<String> =
+                req.configuration_aliases.iter().map(provider_key).collect();
+            aliases.sort();
+
+            let mut entry = vec![
+                "provider".to_string(),
+                name.clone(),
+                effective_source(name, req),
+            ];
+            entry.extend(constraints);
+            entry.push(String::new());
+            entry.extend(aliases);
+            entries.push(entry);
+        }
+
+        for (key, expr) in &self.terraform_settings {
+            entries.push(vec![
+                "terraform_setting".to_string(),
+                key.clone(),
+                expr.to_string(),
+            ]);
+        }
+
+        for block in &self.terraform_nested_blocks {
+            entries.push(vec!["terraform_block".to_string(), block_string(block)]);
+        }
+
+        if let Some(encryption) = &self.encryption {
+            entries.push(vec!["encryption".to_string()]);
+            for key_provider in &encryption.key_providers {
+                entries.push(encryption_block_entry("key_provider", key_provider));
+            }
+            for method in &encryption.methods {
+                entries.push(encryption_block_entry("method", method));
+            }
+            for block in &encryption.nested_blocks {
+                entries.push(vec!["encryption_block".to_string(), block_string(block)]);
+            }
+        }
+
+        if let Some(backend) = &self.backend {
+            let mut entry = vec!["backend".to_string(), backend.type_.clone()];
+            entry.extend(sorted_attributes(&backend.config));
+            entries.push(entry);
+        }
+
+        for config in self.provider_configs.values() {
+            let mut entry = vec![
+                "provider_config".to_string(),
+                config.key(),
+                config.version.clone().unwrap_or_default(),
+            ];
+            entry.extend(sorted_attributes(&config.attributes));
+            entry.extend(nested_block_strings(&config.nested_blocks));
+            entries.push(entry);
+        }
+
+        for variable in self.variables.values() {
+            entries.push(vec![
+                "variable".to_string(),
+                variable.name.clone(),
+                variable.description.clone(),
+                optional_string(variable.type_.as_ref()),
+                optional_string(variable.default.as_ref()),
+                optional_string(variable.sensitive.as_ref()),
+                optional_string(variable.nullable.as_ref()),
+                optional_string(variable.ephemeral.as_ref()),
+            ]);
+        }
+
+        for output in self.outputs.values() {
+            entries.push(vec![
+                "output".to_string(),
+                output.name.clone(),
+                output.description.clone(),
+                output.sensitive.to_string(),
+                optional_string(output.value.as_ref()),
+            ]);
+        }
+
+        for resource in self.managed_resources.values() {
+            let mut entry = vec![
+                "resource".to_string(),
+                resource.address(),
+                resource
+                    .provider
+                    .as_ref()
+                    .map(provider_key)
+                    .unwrap_or_default(),
+                optional_string(resource.count.as_ref()),
+                optional_string(resource.for_each.as_ref()),
+                format!("{:?}", resource.ignore_changes),
+            ];
+            entry.extend(sorted(&resource.depends_on));
+            entry.extend(sorted_attributes(&resource.attributes));
+            entry.extend(nested_block_strings(&resource.nested_blocks));
+            entries.push(entry);
+        }
+
+        for resource in self.data_resources.values() {
+            let mut entry = vec![
+                "data".to_string(),
+                resource.address(),
+                resource
+                    .provider
+                    .as_ref()
+                    .map(provider_key)
+                    .unwrap_or_default(),
+                optional_string(resource.count.as_ref()),
+                optional_string(resource.for_each.as_ref()),
+            ];
+            entry.extend(sorted(&resource.depends_on));
+            entry.extend(sorted_attributes(&resource.attributes));
+            entry.extend(nested_block_strings(&resource.nested_blocks));
+            entries.push(entry);
+        }
+
+        for call in self.module_calls.values() {
+            let mut entry = vec![
+                "module".to_string(),
+                call.name.clone(),
+                call.source.clone(),
+                call.version.clone(),
+                optional_string(call.count.as_ref()),
+                optional_string(call.for_each.as_ref()),
+            ];
+            entry.extend(sorted(&call.depends_on));
+            entries.push(entry);
+        }
+
+        for moved in &self.moved_blocks {
+            entries.push(vec![
+                "moved".to_string(),
+                moved.from.clone(),
+                moved.to.clone(),
+            ]);
+        }
+
+        for import in &self.import_blocks {
+            entries.push(vec![
+                "import".to_string(),
+                import.to.clone(),
+                optional_string(import.id.as_ref()),
+            ]);
+        }
+
+        for removed in &self.removed_blocks {
+            entries.push(vec!["removed".to_string(), removed.from.clone()]);
+        }
+
+        entries.sort();
+
+        let mut hash = FNV_OFFSET_BASIS;
+        for entry in &entries {
+            for field in entry {
+                hash = fnv1a(hash, field.as_bytes());
+                // Separate fields so that `["ab", "c"]` and `["a", "bc"]` hash differently.
+                hash = fnv1a(hash, &[0xff]);
+            }
+            hash = fnv1a(hash, &[0xfe]);
+        }
+
+        hash
+    }
+}
+
+fn fnv1a(mut hash: u64, bytes: &[u8]) -> u64 {
+    for byte in bytes {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+fn optional_string<T: ToString>(value: Option<&T>) -> String {
+    value.map(ToString::to_string).unwrap_or_default()
+}
+
+fn sorted(values: &[String]) -> Vec<String> {
+    let mut values = values.to_vec();
+    values.sort();
+    values
+}
+
+/// Returns `name=expression` for each of the given attributes, sorted by name.
+fn sorted_attributes(attributes: &HashMap<String, hcl::Expression>) -> Vec<String> {
+    let mut attributes: Vec<String> = attributes
+        .iter()
+        .map(|(key, expr)| format!("{key}={expr}"))
+        .collect();
+    attributes.sort();
+    attributes
+}
+
+/// Returns each nested block formatted as HCL, in source order, as the order of blocks of the
+/// same type can be significant.
+fn nested_block_strings(nested_blocks: &[(String, hcl::Body)]) -> Vec<String> {
+    nested_blocks
+        .iter()
+        .map(|(type_, body)| {
+            format!(
+                "{type_}{{{}}}",
+                hcl::format::to_string(body).unwrap_or_default()
+            )
+        })
+        .collect()
+}
+
+/// Returns the given block formatted as HCL.
+fn block_string(block: &hcl::Block) -> String {
+    let body = hcl::Body::builder().add_block(block.clone()).build();
+    hcl::format::to_string(&body).unwrap_or_default()
+}
+
+fn encryption_block_entry(kind: &str, block: &EncryptionBlock) -> Vec<String> {
+    let mut entry = vec![
+        format!("encryption_{kind}"),
+        block.type_.clone(),
+        block.name.clone(),
+    ];
+    entry.extend(sorted_attributes(&block.attributes));
+    entry
+}
+
+fn provider_key(provider: &ProviderRef) -> String {
+    format!("{}.{}", provider.name, provider.alias)
+}
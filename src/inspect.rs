@@ -0,0 +1,287 @@
+use crate::{Diagnostic, DiagnosticSeverity, Module, ProviderRef};
+use std::collections::BTreeMap;
+
+/// A view of a [`Module`][Module] whose fields and nesting mirror the `Module` struct of
+/// terraform-config-inspect, for projects porting from the Go library.
+///
+/// Source positions are only kept for diagnostics, as they are not tracked for other objects.
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct InspectModule {
+    pub path: String,
+    pub variables: BTreeMap<String, InspectVariable>,
+    pub outputs: BTreeMap<String, InspectOutput>,
+    pub required_core: Vec<String>,
+    pub required_providers: BTreeMap<String, InspectProviderRequirement>,
+    /// Provider configurations, keyed by `name` or `name.alias`.
+    pub provider_configs: BTreeMap<String, InspectProviderConfig>,
+    /// Managed resources, keyed by `type.name`.
+    pub managed_resources: BTreeMap<String, InspectResource>,
+    /// Data resources, keyed by `data.type.name`.
+    pub data_resources: BTreeMap<String, InspectResource>,
+    pub module_calls: BTreeMap<String, InspectModuleCall>,
+    pub diagnostics: Vec<InspectDiagnostic>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct InspectVariable {
+    pub name: String,
+    /// The type constraint as written, or an empty string if none is declared.
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    pub type_: String,
+    pub description: String,
+    pub default: Option<hcl::Expression>,
+    pub required: bool,
+    pub sensitive: bool,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct InspectOutput {
+    pub name: String,
+    pub description: String,
+    pub sensitive: bool,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct InspectProviderRequirement {
+    pub source: String,
+    pub version_constraints: Vec<String>,
+    pub configuration_aliases: Vec<InspectProviderRef>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct InspectProviderRef {
+    pub name: String,
+    pub alias: String,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct InspectProviderConfig {
+    pub name: String,
+    pub alias: String,
+}
+
+/// Whether an [`InspectResource`][InspectResource] is a managed or a data resource.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum InspectResourceMode {
+    #[default]
+    Managed,
+    Data,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct InspectResource {
+    pub mode: InspectResourceMode,
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    pub type_: String,
+    pub name: String,
+    /// The provider configuration selected by the `provider` meta-argument, or the provider
+    /// implied by the resource type.
+    pub provider: InspectProviderRef,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct InspectModuleCall {
+    pub name: String,
+    pub source: String,
+    pub version: String,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct InspectDiagnostic {
+    /// Either `error` or `warning`.
+    pub severity: String,
+    pub summary: String,
+    pub detail: String,
+    pub pos: Option<InspectSourcePos>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct InspectSourcePos {
+    pub filename: String,
+    pub line: usize,
+}
+
+impl Module {
+    /// Converts the module into an [`InspectModule`][InspectModule].
+    pub fn into_inspect(self) -> InspectModule {
+        InspectModule::from(&self)
+    }
+}
+
+impl From<&ProviderRef> for InspectProviderRef {
+    fn from(provider: &ProviderRef) -> Self {
+        Self {
+            name: provider.name.clone(),
+            alias: provider.alias.clone(),
+        }
+    }
+}
+
+impl From<&Diagnostic> for InspectDiagnostic {
+    fn from(diagnostic: &Diagnostic) -> Self {
+        let severity = match diagnostic.severity {
+            DiagnosticSeverity::Error => "error",
+            DiagnosticSeverity::Warning => "warning",
+        };
+
+        Self {
+            severity: severity.to_string(),
+            summary: diagnostic.summary.clone(),
+            detail: diagnostic.detail.clone(),
+            pos: diagnostic.pos.as_ref().map(|pos| InspectSourcePos {
+                filename: pos.filename.display().to_string(),
+                line: pos.line,
+            }),
+        }
+    }
+}
+
+impl From<&Module> for InspectModule {
+    fn from(module: &Module) -> Self {
+        let variables = module
+            .variables
+            .iter()
+            .map(|(name, variable)| {
+                let variable = InspectVariable {
+                    name: variable.name.clone(),
+                    type_: variable
+                        .type_
+                        .as_ref()
+                        .map(ToString::to_string)
+                        .unwrap_or_default(),
+                    description: variable.description.clone(),
+                    default: variable.default.clone(),
+                    required: variable.is_required(),
+                    sensitive: variable.sensitive.unwrap_or_default(),
+                };
+                (name.clone(), variable)
+            })
+            .collect();
+
+        let outputs = module
+            .outputs
+            .iter()
+            .map(|(name, output)| {
+                let output = InspectOutput {
+                    name: output.name.clone(),
+                    description: output.description.clone(),
+                    sensitive: output.sensitive,
+                };
+                (name.clone(), output)
+            })
+            .collect();
+
+        let required_providers = module
+            .required_providers
+            .iter()
+            .map(|(name, req)| {
+                let req = InspectProviderRequirement {
+                    source: req.source.clone(),
+                    version_constraints: req.version_constraints.clone(),
+                    configuration_aliases: req
+                        .configuration_aliases
+                        .iter()
+                        .map(InspectProviderRef::from)
+                        .collect(),
+                };
+                (name.clone(), req)
+            })
+            .collect();
+
+        let provider_configs = module
+            .provider_configs
+            .iter()
+            .map(|(key, config)| {
+                let config = InspectProviderConfig {
+                    name: config.name.clone(),
+                    alias: config.alias.clone(),
+                };
+                (key.clone(), config)
+            })
+            .collect();
+
+        let managed_resources = module
+            .managed_resources
+            .iter()
+            .map(|(key, resource)| {
+                let provider = match &resource.provider {
+                    Some(provider) => InspectProviderRef::from(provider),
+                    None => InspectProviderRef {
+                        name: resource.implied_provider().to_string(),
+                        alias: String::new(),
+                    },
+                };
+                let resource = InspectResource {
+                    mode: InspectResourceMode::Managed,
+                    type_: resource.type_.clone(),
+                    name: resource.name.clone(),
+                    provider,
+                };
+                (key.clone(), resource)
+            })
+            .collect();
+
+        let data_resources = module
+            .data_resources
+            .iter()
+            .map(|(key, resource)| {
+                let provider = match &resource.provider {
+                    Some(provider) => InspectProviderRef::from(provider),
+                    None => InspectProviderRef {
+                        name: resource.implied_provider().to_string(),
+                        alias: String::new(),
+                    },
+                };
+                let resource = InspectResource {
+                    mode: InspectResourceMode::Data,
+                    type_: resource.type_.clone(),
+                    name: resource.name.clone(),
+                    provider,
+                };
+                (key.clone(), resource)
+            })
+            .collect();
+
+        let module_calls = module
+            .module_calls
+            .iter()
+            .map(|(name, call)| {
+                let call = InspectModuleCall {
+                    name: call.name.clone(),
+                    source: call.source.clone(),
+                    version: call.version.clone(),
+                };
+                (name.clone(), call)
+            })
+            .collect();
+
+        Self {
+            path: module.path.display().to_string(),
+            variables,
+            outputs,
+            required_core: module.required_core.clone(),
+            required_providers,
+            provider_configs,
+            managed_resources,
+            data_resources,
+            module_calls,
+            diagnostics: module
+                .diagnostics
+                .iter()
+                .map(InspectDiagnostic::from)
+                .collect(),
+        }
+    }
+}
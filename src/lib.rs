@@ -1,18 +1,120 @@
 use hcl::ObjectKey;
 use std::{
-    collections::HashMap,
-    error, fs, io,
+    collections::{hash_map, HashMap, HashSet},
+    error, fmt, fs, io, panic,
     path::{Path, PathBuf},
+    thread,
 };
 use thiserror::Error;
 
+#[cfg(feature = "archive")]
+mod archive;
+mod backend;
+mod diagnostic;
+mod discover;
+mod encryption;
+mod graph;
+mod hash;
+mod inspect;
+mod lock;
+mod module_call;
+mod options;
+mod output;
+pub mod prelude;
+mod provider_config;
+mod references;
+mod resource;
+mod rich;
+#[cfg(feature = "json-schema")]
+mod schema;
+mod source;
+mod state;
+mod summary;
+mod tfvars;
+mod tree;
+mod variable;
+mod version;
+
+#[cfg(feature = "archive")]
+pub use archive::{load_module_from_archive, load_module_from_archive_with_options, ArchiveFormat};
+pub use backend::Backend;
+pub use diagnostic::{Diagnostic, DiagnosticSeverity, SourcePos};
+pub use discover::discover_modules;
+pub use encryption::{Encryption, EncryptionBlock};
+pub use inspect::{
+    InspectDiagnostic, InspectModule, InspectModuleCall, InspectOutput, InspectProviderConfig,
+    InspectProviderRef, InspectProviderRequirement, InspectResource, InspectResourceMode,
+    InspectSourcePos, InspectVariable,
+};
+pub use lock::{LockFile, LockedProvider, LOCK_FILE_NAME};
+pub use module_call::ModuleCall;
+pub use options::{LoadOptions, ReadErrorPolicy};
+pub use output::Output;
+pub use provider_config::ProviderConfig;
+pub use resource::{DataResource, IgnoreChanges, ManagedResource};
+pub use rich::{load_module_rich, RichModule};
+pub use source::{SourceAddr, BUILTIN_PROVIDER_HOST, DEFAULT_PROVIDER_HOST};
+pub use state::{ImportBlock, MovedBlock, RemovedBlock};
+pub use summary::{ModuleSummary, ProviderSummary};
+pub use tree::{load_module_tree, ModuleTree};
+pub use variable::{VarType, Variable};
+pub use version::Pinning;
+
 type Result<T> = std::result::Result<T, Error>;
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct Module {
     pub path: PathBuf,
+    /// Terraform version constraints from every `required_version`, in load order. A constraint
+    /// declared more than once is only kept the first time.
     pub required_core: Vec<String>,
+    /// Files that declared each entry of `required_core`, at the same index.
+    pub required_core_origins: Vec<PathBuf>,
     pub required_providers: HashMap<String, ProviderRequirement>,
+    /// Settings in `terraform` blocks that are not otherwise modeled, keyed by name.
+    pub terraform_settings: HashMap<String, hcl::Expression>,
+    /// The `backend` block nested in a `terraform` block, if any.
+    pub backend: Option<Backend>,
+    /// The OpenTofu `encryption` block nested in a `terraform` block, if any.
+    pub encryption: Option<Encryption>,
+    /// Blocks nested in `terraform` blocks that are not otherwise modeled, in load order.
+    pub terraform_nested_blocks: Vec<hcl::Block>,
+    /// Input variables, keyed by name.
+    pub variables: HashMap<String, Variable>,
+    /// Values assigned to input variables in the module's variable definitions files, such as
+    /// `terraform.tfvars`, keyed by variable name. Only read when
+    /// [`LoadOptions::load_variable_values`][LoadOptions::load_variable_values] is set.
+    pub variable_values: HashMap<String, hcl::Expression>,
+    /// Names of the input variables referenced as `var.name` in any block of the module, other
+    /// than the variable's own `variable` block.
+    pub referenced_variables: HashSet<String>,
+    /// Output values, keyed by name.
+    pub outputs: HashMap<String, Output>,
+    /// Provider configurations, keyed by `name` or `name.alias`.
+    pub provider_configs: HashMap<String, ProviderConfig>,
+    /// Managed resources, keyed by `type.name`.
+    pub managed_resources: HashMap<String, ManagedResource>,
+    /// Data resources, keyed by `data.type.name`.
+    pub data_resources: HashMap<String, DataResource>,
+    /// Calls to other modules, keyed by name.
+    pub module_calls: HashMap<String, ModuleCall>,
+    /// `moved` blocks, in load order.
+    ///
+    /// Each state-mutation block is independent, so blocks in override files are appended to
+    /// those in primary files rather than replacing them. The same applies to `import` and
+    /// `removed` blocks.
+    pub moved_blocks: Vec<MovedBlock>,
+    /// `import` blocks, in load order.
+    pub import_blocks: Vec<ImportBlock>,
+    /// `removed` blocks, in load order.
+    pub removed_blocks: Vec<RemovedBlock>,
+    /// The module's dependency lock file, if one was found next to its configuration files and
+    /// [`LoadOptions::load_lock_file`][LoadOptions::load_lock_file] is set.
+    pub lock_file: Option<LockFile>,
+    /// Problems found while loading the module that did not stop it from loading.
+    pub diagnostics: Vec<Diagnostic>,
+    /// Files that contributed at least one block to the module, in load order.
+    pub source_files: Vec<PathBuf>,
 }
 
 impl Module {
@@ -22,13 +124,488 @@ impl Module {
             ..Default::default()
         }
     }
+
+    /// Returns the files that contributed at least one block to the module, in load order.
+    ///
+    /// Files that failed to parse, or only contain blocks this crate does not model, are not
+    /// included.
+    pub fn touched_files(&self) -> &[PathBuf] {
+        &self.source_files
+    }
+
+    /// Parses the given string as the contents of the file at the given path and merges it into the
+    /// module.
+    ///
+    /// As when loading a directory, declarations from an override file, such as `override.tf`,
+    /// are merged into those already in the module argument by argument, so override files
+    /// should be appended after primary files. Unlike loading a directory, the inline `version`
+    /// of `provider` blocks is not merged into the provider requirements.
+    ///
+    /// # Arguments
+    ///
+    /// * `contents` - Contents of the file
+    /// * `path` - Path of the file the contents were read from, used in errors and diagnostics
+    pub fn append_from_str(&mut self, contents: &str, path: &Path) -> Result<()> {
+        load_file_contents(path, contents, &LoadOptions::new().strict(true), self)
+    }
+
+    /// Returns the module if it has no error diagnostics, or an [`Error::Multiple`][Error::Multiple]
+    /// holding them otherwise.
+    ///
+    /// This allows loading a module non-strictly while still failing on any errors found.
+    pub fn into_result(self) -> Result<Module> {
+        let errors: Vec<Diagnostic> = self
+            .diagnostics
+            .iter()
+            .filter(|diag| diag.severity == DiagnosticSeverity::Error)
+            .cloned()
+            .collect();
+
+        if errors.is_empty() {
+            Ok(self)
+        } else {
+            Err(Error::Multiple(errors))
+        }
+    }
+
+    /// Returns each Terraform version constraint in `required_core` along with the file that
+    /// declared it.
+    pub fn required_core_with_origins(&self) -> impl Iterator<Item = (&str, &Path)> {
+        self.required_core
+            .iter()
+            .zip(&self.required_core_origins)
+            .map(|(constraint, origin)| (constraint.as_str(), origin.as_path()))
+    }
+
+    /// Returns every Terraform version constraint in `required_core` joined into a single string,
+    /// such as `>= 1.3.0, < 2.0.0`, or `None` if there are none.
+    ///
+    /// Constraints are kept in load order, and a constraint repeated within a comma-separated entry
+    /// is only included once.
+    pub fn effective_terraform_version_requirement(&self) -> Option<String> {
+        let mut constraints = Vec::new();
+        for constraint in &self.required_core {
+            push_version_constraints(&mut constraints, constraint);
+        }
+
+        if constraints.is_empty() {
+            None
+        } else {
+            Some(constraints.join(", "))
+        }
+    }
+
+    /// Returns whether the given Terraform version, such as `1.6.0` or `1.6.0-beta1`, satisfies
+    /// every constraint in `required_core`. A module without constraints allows any version.
+    ///
+    /// As in Terraform, a pre-release version only satisfies constraints that name a pre-release
+    /// of the same version, while a release satisfies constraints on its own pre-releases, so
+    /// `>= 1.6.0-beta1` allows `1.6.0-beta2` and `1.6.0` but not `1.7.0-beta1`.
+    ///
+    /// Returns an [`Error::InvalidVersion`][Error::InvalidVersion],
+    /// [`Error::InvalidVersionConstraint`][Error::InvalidVersionConstraint] or
+    /// [`Error::EmptyVersionConstraint`][Error::EmptyVersionConstraint] if the version or any
+    /// constraint cannot be parsed.
+    pub fn allows_terraform_version(&self, version: &str) -> Result<bool> {
+        version::matches_all(&self.required_core, version)
+    }
+
+    /// Returns the number of providers required by the module.
+    pub fn provider_requirement_count(&self) -> usize {
+        self.required_providers.len()
+    }
+
+    /// Returns the number of input variables declared by the module.
+    pub fn variable_count(&self) -> usize {
+        self.variables.len()
+    }
+
+    /// Returns the number of output values declared by the module.
+    pub fn output_count(&self) -> usize {
+        self.outputs.len()
+    }
+
+    /// Returns whether the module looks like a root module, which is the case when it configures
+    /// where its state is stored with a `backend` or `cloud` block.
+    ///
+    /// This is only a heuristic: a root module may rely on the default local backend without
+    /// declaring one, and a child module may be given a backend by mistake, which Terraform then
+    /// ignores.
+    pub fn is_root_module(&self) -> bool {
+        self.backend.is_some()
+            || self
+                .terraform_nested_blocks
+                .iter()
+                .any(|block| block.identifier() == "cloud")
+    }
+
+    /// Returns the names of declared variables that are never referenced in the module, sorted by
+    /// name. A variable only referenced in its own `validation` blocks counts as unused.
+    pub fn unused_variables(&self) -> Vec<String> {
+        let mut unused: Vec<String> = self
+            .variables
+            .keys()
+            .filter(|name| !self.referenced_variables.contains(*name))
+            .cloned()
+            .collect();
+        unused.sort();
+        unused
+    }
+
+    /// Returns the number of managed resources declared by the module, excluding data resources.
+    pub fn resource_count(&self) -> usize {
+        self.managed_resources.len()
+    }
+
+    /// Returns the managed resources that use the provider with the given local name, either
+    /// through the `provider` meta-argument or, without one, through their type, sorted by
+    /// address.
+    pub fn resources_using_provider(&self, name: &str) -> Vec<&ManagedResource> {
+        let mut resources: Vec<_> = self
+            .managed_resources
+            .values()
+            .filter(|resource| resource.provider_name() == name)
+            .collect();
+        resources.sort_by_key(|resource| resource.address());
+        resources
+    }
+
+    /// Returns the data resources that use the provider with the given local name, either through
+    /// the `provider` meta-argument or, without one, through their type, sorted by address.
+    pub fn data_resources_using_provider(&self, name: &str) -> Vec<&DataResource> {
+        let mut resources: Vec<_> = self
+            .data_resources
+            .values()
+            .filter(|resource| resource.provider_name() == name)
+            .collect();
+        resources.sort_by_key(|resource| resource.address());
+        resources
+    }
+
+    /// Returns the names of the outputs that are not marked `sensitive` but whose value references
+    /// a variable that is, sorted by name.
+    ///
+    /// Terraform rejects such outputs when planning, so this catches them without evaluating the
+    /// configuration. Only direct references to `var.<name>` are considered.
+    pub fn outputs_leaking_sensitive(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .outputs
+            .values()
+            .filter(|output| !output.sensitive)
+            .filter(|output| {
+                output.referenced_addresses().iter().any(|address| {
+                    let mut parts = address.split('.');
+                    parts.next() == Some("var")
+                        && parts
+                            .next()
+                            .and_then(|name| self.variables.get(name))
+                            .is_some_and(|variable| variable.sensitive == Some(true))
+                })
+            })
+            .map(|output| output.name.clone())
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// Returns each provider's local name mapped to its effective source address, with implied
+    /// sources resolved and the default registry hostname filled in.
+    pub fn provider_source_map(&self) -> HashMap<String, String> {
+        self.required_providers
+            .iter()
+            .map(|(name, req)| (name.clone(), effective_source(name, req)))
+            .collect()
+    }
+
+    /// Returns each effective source address mapped to the local names of the providers that use
+    /// it, sorted by name.
+    ///
+    /// This is the inverse of [`provider_source_map`][Module::provider_source_map].
+    pub fn providers_by_source(&self) -> HashMap<String, Vec<String>> {
+        let mut by_source: HashMap<String, Vec<String>> = HashMap::new();
+        for (name, source) in self.provider_source_map() {
+            by_source.entry(source).or_default().push(name);
+        }
+        for names in by_source.values_mut() {
+            names.sort();
+        }
+
+        by_source
+    }
+
+    /// Returns the local names of providers that were required with more than one distinct source
+    /// across files, sorted by name, each with its effective sources in the order they were seen.
+    ///
+    /// Each collision is also recorded as an error diagnostic when the module is loaded.
+    pub fn provider_local_name_collisions(&self) -> Vec<(String, Vec<String>)> {
+        self.required_providers_sorted()
+            .into_iter()
+            .filter(|(_, req)| !req.conflicting_sources.is_empty())
+            .map(|(name, req)| {
+                let mut sources = vec![effective_source(name, req)];
+                sources.extend(req.conflicting_sources.iter().cloned());
+                (name.to_string(), sources)
+            })
+            .collect()
+    }
+
+    /// Returns the requirement for the provider with the given source address, whatever its local
+    /// name. Sources are compared case-insensitively, with the default registry hostname filled
+    /// in, so `hashicorp/random` matches `registry.terraform.io/HashiCorp/random`.
+    ///
+    /// If several providers share the source, the one with the first local name is returned.
+    pub fn provider_requirement_for_source(&self, source: &str) -> Option<&ProviderRequirement> {
+        let source = match SourceAddr::parse(source) {
+            Some(addr) => addr.to_string(),
+            None => source.to_string(),
+        }
+        .to_lowercase();
+
+        self.required_providers_sorted()
+            .into_iter()
+            .find(|(name, req)| effective_source(name, req).to_lowercase() == source)
+            .map(|(_, req)| req)
+    }
+
+    /// Returns every version constraint that applies to the given provider, gathered from its
+    /// `required_providers` entries and the inline `version` of its `provider` blocks.
+    ///
+    /// As when loading a directory, inline versions are only included for a provider without any
+    /// constraints in `required_providers`, since Terraform otherwise ignores them.
+    pub fn provider_constraints_for(&self, name: &str) -> Vec<String> {
+        let mut constraints = vec![];
+        if let Some(req) = self.required_providers.get(name) {
+            for constraint in &req.version_constraints {
+                push_version_constraints(&mut constraints, constraint);
+            }
+        }
+        if !constraints.is_empty() {
+            return constraints;
+        }
+
+        let mut configs: Vec<&ProviderConfig> = self
+            .provider_configs
+            .values()
+            .filter(|config| config.name == name)
+            .collect();
+        configs.sort_by_key(|config| config.key());
+        for version in configs.iter().filter_map(|config| config.version.as_ref()) {
+            push_version_constraints(&mut constraints, version);
+        }
+
+        constraints
+    }
+
+    /// Returns the local names of providers without an explicit `source`, which Terraform resolves
+    /// to an implied `hashicorp/` source, sorted by name.
+    pub fn providers_missing_source(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .required_providers
+            .iter()
+            .filter(|(_, req)| req.source.is_empty())
+            .map(|(name, _)| name.clone())
+            .collect();
+        names.sort();
+
+        names
+    }
+
+    /// Returns the local names of providers whose source is not in the given allowlist of known
+    /// sources, sorted by name, along with the closest known source if one is similar enough to
+    /// suggest a typo.
+    ///
+    /// Sources are compared in their fully-qualified form, so `hashicorp/aws` and
+    /// `registry.terraform.io/hashicorp/aws` are equivalent. Builtin providers are never reported.
+    pub fn suspicious_provider_sources(
+        &self,
+        known: &HashSet<String>,
+    ) -> Vec<(String, Option<String>)> {
+        let mut known: Vec<(String, &String)> = known
+            .iter()
+            .map(|source| {
+                let normalized = SourceAddr::parse(source)
+                    .map(|addr| addr.to_string())
+                    .unwrap_or_else(|| source.clone());
+                (normalized, source)
+            })
+            .collect();
+        known.sort();
+
+        let mut suspicious: Vec<(String, Option<String>)> = self
+            .required_providers
+            .iter()
+            .filter(|(_, req)| !req.is_builtin())
+            .filter_map(|(name, req)| {
+                let source = effective_source(name, req);
+                if known.iter().any(|(normalized, _)| *normalized == source) {
+                    return None;
+                }
+
+                let suggestion = known
+                    .iter()
+                    .map(|(normalized, original)| (edit_distance(&source, normalized), original))
+                    .filter(|(distance, _)| *distance <= MAX_SUGGESTION_DISTANCE)
+                    .min_by_key(|(distance, _)| *distance)
+                    .map(|(_, original)| (*original).clone());
+
+                Some((name.clone(), suggestion))
+            })
+            .collect();
+        suspicious.sort();
+
+        suspicious
+    }
+
+    /// Returns every aliased provider configuration the module declares in `provider` blocks or
+    /// expects through `configuration_aliases`, deduplicated and sorted by name and alias.
+    pub fn provider_aliases(&self) -> Vec<ProviderRef> {
+        let from_requirements = self
+            .required_providers
+            .values()
+            .flat_map(|req| req.configuration_aliases.iter().cloned());
+        let from_configs = self
+            .provider_configs
+            .values()
+            .filter(|config| !config.alias.is_empty())
+            .map(|config| ProviderRef::new(config.name.clone(), config.alias.clone()));
+
+        let mut seen = HashSet::new();
+        let mut aliases: Vec<ProviderRef> = from_requirements
+            .chain(from_configs)
+            .filter(|alias| seen.insert(alias.clone()))
+            .collect();
+        aliases.sort_by(|a, b| (&a.name, &a.alias).cmp(&(&b.name, &b.alias)));
+
+        aliases
+    }
+
+    /// Returns an iterator over the module's provider requirements, keyed by local name.
+    ///
+    /// This is the preferred way to traverse provider requirements, as it does not depend on the
+    /// type of map used to store them.
+    pub fn providers(&self) -> Providers<'_> {
+        Providers {
+            inner: self.required_providers.iter(),
+        }
+    }
+
+    /// Returns an iterator over the module's provider requirements, keyed by local name, that
+    /// allows each requirement to be modified in place.
+    pub fn providers_iter_mut(&mut self) -> ProvidersMut<'_> {
+        ProvidersMut {
+            inner: self.required_providers.iter_mut(),
+        }
+    }
+
+    /// Returns the module's provider requirements, keyed by local name and sorted by it.
+    pub fn required_providers_sorted(&self) -> Vec<(&str, &ProviderRequirement)> {
+        let mut providers: Vec<_> = self
+            .required_providers
+            .iter()
+            .map(|(name, req)| (name.as_str(), req))
+            .collect();
+        providers.sort_by_key(|(name, _)| *name);
+        providers
+    }
 }
 
-#[derive(Debug, Default)]
+impl<'a> IntoIterator for &'a Module {
+    type Item = (&'a String, &'a ProviderRequirement);
+    type IntoIter = Providers<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.providers()
+    }
+}
+
+/// Prints a short multi-line summary of the module, listing its providers in name order along with
+/// the number of each other kind of element.
+impl fmt::Display for Module {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Module {}", self.path.display())?;
+
+        write!(f, "  Terraform: {} constraint(s)", self.required_core.len())?;
+        match self.effective_terraform_version_requirement() {
+            Some(constraints) => writeln!(f, " ({constraints})")?,
+            None => writeln!(f)?,
+        }
+
+        let providers = self.required_providers_sorted();
+        writeln!(f, "  Providers: {}", providers.len())?;
+        for (name, req) in providers {
+            write!(f, "    {name}: {}", effective_source(name, req))?;
+            if req.version_constraints.is_empty() {
+                writeln!(f)?;
+            } else {
+                writeln!(f, " ({})", req.version_constraints.join(", "))?;
+            }
+        }
+
+        writeln!(f, "  Variables: {}", self.variables.len())?;
+        writeln!(f, "  Outputs: {}", self.outputs.len())?;
+        writeln!(f, "  Managed resources: {}", self.managed_resources.len())?;
+        writeln!(f, "  Data resources: {}", self.data_resources.len())?;
+        writeln!(f, "  Module calls: {}", self.module_calls.len())?;
+        write!(f, "  Diagnostics: {}", self.diagnostics.len())
+    }
+}
+
+/// Iterator over the provider requirements of a [`Module`][Module], created by
+/// [`Module::providers`][Module::providers].
+#[derive(Debug, Clone)]
+pub struct Providers<'a> {
+    inner: hash_map::Iter<'a, String, ProviderRequirement>,
+}
+
+impl<'a> Iterator for Providers<'a> {
+    type Item = (&'a String, &'a ProviderRequirement);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl ExactSizeIterator for Providers<'_> {}
+
+/// Iterator over mutable provider requirements of a [`Module`][Module], created by
+/// [`Module::providers_iter_mut`][Module::providers_iter_mut].
+#[derive(Debug)]
+pub struct ProvidersMut<'a> {
+    inner: hash_map::IterMut<'a, String, ProviderRequirement>,
+}
+
+impl<'a> Iterator for ProvidersMut<'a> {
+    type Item = (&'a String, &'a mut ProviderRequirement);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl ExactSizeIterator for ProvidersMut<'_> {}
+
+#[derive(Debug, Default, Clone)]
 pub struct ProviderRequirement {
     pub source: String,
     pub version_constraints: Vec<String>,
     pub configuration_aliases: Vec<ProviderRef>,
+    /// Whether `source` is not a static string, such as `"${local.host}/hashicorp/aws"`. The
+    /// source then holds the raw template or expression.
+    pub source_is_computed: bool,
+    /// The local directory the provider is loaded from instead, if its source matches one of the
+    /// [`LoadOptions::dev_overrides`][LoadOptions::dev_overrides].
+    pub dev_override: Option<PathBuf>,
+    /// Other effective source addresses the provider was required with in later files, which
+    /// were ignored in favor of `source`.
+    pub conflicting_sources: Vec<String>,
 }
 
 impl ProviderRequirement {
@@ -37,11 +614,120 @@ impl ProviderRequirement {
             source,
             version_constraints,
             configuration_aliases: vec![],
+            source_is_computed: false,
+            dev_override: None,
+            conflicting_sources: vec![],
+        }
+    }
+
+    /// Sets the requirement's source address.
+    pub fn with_source(mut self, source: String) -> Self {
+        self.source = source;
+        self
+    }
+
+    /// Adds a version constraint, which may be a comma-separated list of constraints. Constraints
+    /// that are already present are not added again.
+    pub fn add_version_constraint(mut self, constraint: &str) -> Self {
+        push_version_constraints(&mut self.version_constraints, constraint);
+        self
+    }
+
+    /// Sets the provider configurations the module expects to be passed by its callers.
+    pub fn with_configuration_aliases(mut self, configuration_aliases: Vec<ProviderRef>) -> Self {
+        self.configuration_aliases = configuration_aliases;
+        self
+    }
+
+    /// Creates a requirement for a provider that has no explicit `source`.
+    fn implied(local_name: &str) -> Self {
+        Self {
+            source: builtin_provider_source(local_name).unwrap_or_default(),
+            ..Default::default()
+        }
+    }
+
+    /// Parses the requirement's source address, returning `None` if it is empty or invalid.
+    pub fn source_parts(&self) -> Option<SourceAddr> {
+        SourceAddr::parse(&self.source)
+    }
+
+    /// Returns whether the given version of the provider satisfies all of the requirement's
+    /// version constraints. A requirement without constraints allows any version.
+    ///
+    /// Returns an [`Error::InvalidVersion`][Error::InvalidVersion],
+    /// [`Error::InvalidVersionConstraint`][Error::InvalidVersionConstraint] or
+    /// [`Error::EmptyVersionConstraint`][Error::EmptyVersionConstraint] if the version or any
+    /// constraint cannot be parsed.
+    pub fn matches_version(&self, version: &str) -> Result<bool> {
+        version::matches_all(&self.version_constraints, version)
+    }
+
+    /// Classifies how strictly the requirement's version constraints pin the provider's version.
+    pub fn pinning(&self) -> Pinning {
+        version::pinning(&self.version_constraints)
+    }
+
+    /// Returns whether the requirement refers to a provider built into Terraform, such as the
+    /// `terraform` provider backing `terraform_remote_state`.
+    pub fn is_builtin(&self) -> bool {
+        self.source
+            .starts_with(&format!("{BUILTIN_PROVIDER_HOST}/builtin/"))
+    }
+}
+
+/// Returns the source address Terraform assumes for a provider declared without an explicit
+/// `source`.
+pub fn implied_provider_source(local_name: &str) -> String {
+    builtin_provider_source(local_name).unwrap_or_else(|| format!("hashicorp/{local_name}"))
+}
+
+/// Returns the fully-qualified source address of a provider requirement, resolving an implied
+/// source from the provider's local name if necessary.
+fn effective_source(local_name: &str, req: &ProviderRequirement) -> String {
+    let source = if req.source.is_empty() {
+        implied_provider_source(local_name)
+    } else {
+        req.source.clone()
+    };
+
+    match SourceAddr::parse(&source) {
+        Some(addr) => addr.to_string(),
+        None => source,
+    }
+}
+
+/// Maximum number of edits between an unknown and a known provider source for the known source
+/// to be suggested as a likely correction.
+const MAX_SUGGESTION_DISTANCE: usize = 3;
+
+/// Returns the Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut curr = vec![i + 1; b.len() + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = prev[j] + usize::from(a_char != *b_char);
+            curr[j + 1] = substitution.min(prev[j + 1] + 1).min(curr[j] + 1);
         }
+        prev = curr;
     }
+
+    prev[b.len()]
 }
 
-#[derive(Debug, Default, Clone)]
+/// Builtin providers are never installed from a registry, so their implied source address is
+/// recorded directly rather than being left empty.
+fn builtin_provider_source(local_name: &str) -> Option<String> {
+    match local_name {
+        "terraform" => Some(format!("{BUILTIN_PROVIDER_HOST}/builtin/terraform")),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
 pub struct ProviderRef {
     pub name: String,
     pub alias: String,
@@ -57,7 +743,10 @@ impl ProviderRef {
 pub enum Error {
     #[error(transparent)]
     Io(#[from] io::Error),
-    #[error(transparent)]
+    /// An error from another library, such as one reading an archive. Its
+    /// [`source`][error::Error::source] is the wrapped error itself, so the whole chain can be
+    /// walked.
+    #[error("{0}")]
     Other(#[from] Box<dyn error::Error + Sync + Send>),
     #[error(transparent)]
     Parse(#[from] hcl::Error),
@@ -67,88 +756,700 @@ pub enum Error {
         expr: hcl::Expression,
         file_name: PathBuf,
     },
+    #[error("unexpected {block_type:?} block inside {parent:?} block in {file_name}")]
+    UnexpectedBlock {
+        block_type: String,
+        parent: String,
+        file_name: PathBuf,
+    },
+    #[error("unexpected labels for {block_type:?} block in {file_name}: {labels:?}")]
+    UnexpectedLabels {
+        block_type: String,
+        labels: Vec<String>,
+        file_name: PathBuf,
+    },
+    #[error("file name is not valid UTF-8: {0}")]
+    NonUtf8Path(PathBuf),
+    #[error("module has {} error diagnostic(s)", .0.len())]
+    Multiple(Vec<Diagnostic>),
+    /// A `required_providers` entry has neither a `source` nor a `version`, or its `source` is not
+    /// a valid provider source address. Only returned with
+    /// [`LoadOptions::strict_providers`][LoadOptions::strict_providers].
+    #[error("invalid source {source_addr:?} for provider {name:?}")]
+    InvalidProviderSource { name: String, source_addr: String },
+    #[error("invalid version: {0:?}")]
+    InvalidVersion(String),
+    #[error("invalid version constraint: {0:?}")]
+    InvalidVersionConstraint(String),
+    /// A list of version constraints contains an empty constraint, such as after a trailing comma
+    /// in `">= 1.0, "`.
+    #[error("empty version constraint in {0:?}; check for a stray or trailing comma")]
+    EmptyVersionConstraint(String),
+    #[error("not a directory: {0}")]
+    NotADirectory(PathBuf),
+    /// A file is larger than [`LoadOptions::max_file_size`][LoadOptions::max_file_size].
+    #[error("{path} is {size} bytes, larger than the limit of {limit} bytes")]
+    FileTooLarge {
+        path: PathBuf,
+        size: u64,
+        limit: u64,
+    },
+}
+
+impl Error {
+    /// Returns whether the error is caused by the contents of a single file or module, so that a
+    /// caller loading many modules can reasonably skip the affected one and carry on.
+    ///
+    /// Parse errors, unexpected expressions or labels, invalid provider sources or versions, files
+    /// that are not valid UTF-8 and [`Error::Multiple`][Error::Multiple] are recoverable. Other IO
+    /// errors, [`Error::NotADirectory`][Error::NotADirectory] and [`Error::Other`][Error::Other]
+    /// usually indicate a problem with the environment or the caller, and are not.
+    pub fn is_recoverable(&self) -> bool {
+        match self {
+            Error::Io(e) => e.kind() == io::ErrorKind::InvalidData,
+            Error::Parse(_)
+            | Error::UnexpectedExpr { .. }
+            | Error::UnexpectedBlock { .. }
+            | Error::UnexpectedLabels { .. }
+            | Error::NonUtf8Path(_)
+            | Error::Multiple(_)
+            | Error::InvalidProviderSource { .. }
+            | Error::InvalidVersion(_)
+            | Error::InvalidVersionConstraint(_)
+            | Error::EmptyVersionConstraint(_)
+            | Error::FileTooLarge { .. } => true,
+            Error::Other(_) | Error::NotADirectory(_) => false,
+        }
+    }
 }
 
 /// Reads the directory at the given path and attempts to interpret it as a Terraform module.
 ///
+/// Files are read and parsed one at a time, and their contents are dropped once they have been
+/// interpreted, so at most one file's contents is held in memory at once. The module itself keeps
+/// only the interpreted blocks.
+///
 /// # Arguments
 ///
 /// * `path` - Path to the directory containing the Terraform configuration
-/// * `strict` - Whether to immediately return an error if a file in the directory cannot be parsed
+/// * `strict` - Whether to immediately return an error if a file in the directory cannot be parsed.
+///   Otherwise, the file is skipped and an error diagnostic is recorded on the module.
 pub fn load_module(path: &Path, strict: bool) -> Result<Module> {
-    let mut module = Module::new(path.to_path_buf());
+    load_module_with_options(path, &LoadOptions::new().strict(strict))
+}
 
-    let files = get_files_in_dir(path)?;
+/// Reads the directory at the given path and attempts to interpret it as a Terraform module, using
+/// the given [`LoadOptions`][LoadOptions].
+pub fn load_module_with_options(path: &Path, options: &LoadOptions) -> Result<Module> {
+    load_dir(path, options, |file_name, file_contents, module| {
+        load_file_contents(file_name, file_contents, options, module)
+    })
+}
 
-    for file_name in files {
-        match fs::read_to_string(&file_name) {
-            Ok(file_contents) => {
-                let file = match hcl::parse(&file_contents) {
-                    Ok(body) => body,
-                    Err(e) => match e {
-                        hcl::Error::Parse(e) => {
-                            if strict {
-                                return Err(Error::Parse(hcl::Error::Parse(e)));
-                            } else {
-                                continue;
-                            }
-                        }
-                        _ => return Err(Error::Other(Box::new(e))),
-                    },
-                };
+/// Reads each file of the module in the given directory, passing its contents to `load_file` to be
+/// stored in the module.
+fn load_dir(
+    path: &Path,
+    options: &LoadOptions,
+    mut load_file: impl FnMut(&Path, &str, &mut Module) -> Result<()>,
+) -> Result<Module> {
+    if fs::metadata(path).is_ok_and(|metadata| !metadata.is_dir()) {
+        return Err(Error::NotADirectory(path.to_path_buf()));
+    }
+
+    #[cfg(feature = "tracing")]
+    let _span = tracing::info_span!("load_module", path = %path.display()).entered();
+
+    let module_path = if options.canonicalize_path {
+        fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+    } else {
+        path.to_path_buf()
+    };
+    let mut module = Module::new(module_path);
 
-                load_module_from_file(&file_name, file, &mut module)?;
+    let files = get_files_in_dir(path, options)?;
+    let contents = read_files(&files, options);
+
+    let mut suppressed = 0;
+    for (file_name, file_contents) in files.iter().zip(contents) {
+        match file_contents {
+            Ok(file_contents) => {
+                let file_contents = options.preprocessed(file_name, file_contents);
+                load_file(file_name, &file_contents, &mut module)?
             }
-            Err(e) => {
-                if strict {
-                    return Err(Error::Io(e));
+            Err(e) => handle_read_file_error(file_name, e, options, &mut module)?,
+        }
+
+        cap_diagnostics(options, &mut module, &mut suppressed);
+    }
+
+    if options.load_lock_file {
+        lock::load_lock_file(path, options, &mut module)?;
+    }
+    if options.load_variable_values {
+        tfvars::load_variable_values(path, options, &mut module)?;
+    }
+    apply_dev_overrides(options, &mut module);
+
+    #[cfg(feature = "tracing")]
+    let _merge_span = tracing::debug_span!("merge_inline_provider_versions").entered();
+    provider_config::merge_inline_provider_versions(&mut module);
+
+    cap_diagnostics(options, &mut module, &mut suppressed);
+    if suppressed > 0 {
+        module.diagnostics.push(Diagnostic::new(
+            DiagnosticSeverity::Warning,
+            "Diagnostics suppressed".to_string(),
+            format!("{suppressed} more diagnostic(s) were suppressed."),
+        ));
+    }
+
+    Ok(module)
+}
+
+/// Drops the module's diagnostics beyond
+/// [`LoadOptions::max_diagnostics`][LoadOptions::max_diagnostics], counting them in `suppressed`.
+fn cap_diagnostics(options: &LoadOptions, module: &mut Module, suppressed: &mut usize) {
+    if let Some(max_diagnostics) = options.max_diagnostics {
+        if module.diagnostics.len() > max_diagnostics {
+            *suppressed += module.diagnostics.len() - max_diagnostics;
+            module.diagnostics.truncate(max_diagnostics);
+        }
+    }
+}
+
+/// Records the matching [`LoadOptions::dev_overrides`][LoadOptions::dev_overrides] directory on
+/// each provider requirement, comparing effective source addresses.
+fn apply_dev_overrides(options: &LoadOptions, module: &mut Module) {
+    if options.dev_overrides.is_empty() {
+        return;
+    }
+
+    let dev_overrides: HashMap<String, &PathBuf> = options
+        .dev_overrides
+        .iter()
+        .map(|(source, dir)| match SourceAddr::parse(source) {
+            Some(addr) => (addr.to_string(), dir),
+            None => (source.clone(), dir),
+        })
+        .collect();
+    for (name, req) in module.required_providers.iter_mut() {
+        if let Some(dir) = dev_overrides.get(&effective_source(name, req)) {
+            req.dev_override = Some(dir.to_path_buf());
+        }
+    }
+}
+
+/// Applies the [`ReadErrorPolicy`][ReadErrorPolicy] of the given options to a file that could not
+/// be read.
+fn handle_read_error(
+    file_name: &Path,
+    e: io::Error,
+    options: &LoadOptions,
+    module: &mut Module,
+) -> Result<()> {
+    match options.read_error_policy() {
+        ReadErrorPolicy::Fail => return Err(Error::Io(e)),
+        ReadErrorPolicy::Diagnose => module.diagnostics.push(Diagnostic::new(
+            DiagnosticSeverity::Error,
+            "Failed to read file".to_string(),
+            format!("{} could not be read: {e}", file_name.display()),
+        )),
+        ReadErrorPolicy::Skip => (),
+    }
+
+    #[cfg(feature = "tracing")]
+    tracing::warn!(file = %file_name.display(), error = %e, "skipped file: cannot be read");
+
+    Ok(())
+}
+
+/// Handles an error returned by [`read_file`][read_file], skipping a file that is too large with
+/// an error diagnostic unless loading strictly.
+fn handle_read_file_error(
+    file_name: &Path,
+    e: Error,
+    options: &LoadOptions,
+    module: &mut Module,
+) -> Result<()> {
+    match e {
+        Error::Io(e) => handle_read_error(file_name, e, options, module),
+        e @ Error::FileTooLarge { .. } if !options.strict => {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(file = %file_name.display(), "skipped file: too large");
+
+            module.diagnostics.push(Diagnostic::new(
+                DiagnosticSeverity::Error,
+                "File too large".to_string(),
+                e.to_string(),
+            ));
+            Ok(())
+        }
+        e => Err(e),
+    }
+}
+
+/// Reads the given files, on up to [`LoadOptions::read_threads`][LoadOptions::read_threads]
+/// threads at once, returning their contents in the same order.
+///
+/// When reading on the calling thread only, each file is read lazily as the iterator advances, so
+/// only one file's contents need be held in memory at a time.
+fn read_files<'a>(
+    files: &'a [PathBuf],
+    options: &'a LoadOptions,
+) -> Box<dyn Iterator<Item = Result<String>> + 'a> {
+    if options.read_threads <= 1 || files.len() <= 1 {
+        return Box::new(
+            files
+                .iter()
+                .map(move |file_name| read_file(file_name, options)),
+        );
+    }
+
+    let chunk_size = files.len().div_ceil(options.read_threads);
+    thread::scope(|scope| {
+        let handles: Vec<_> = files
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .map(|file_name| read_file(file_name, options))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        let contents: Vec<_> = handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap_or_else(|e| panic::resume_unwind(e)))
+            .collect();
+        Box::new(contents.into_iter())
+    })
+}
+
+/// Reads the given file, first checking it against
+/// [`LoadOptions::max_file_size`][LoadOptions::max_file_size].
+fn read_file(file_name: &Path, options: &LoadOptions) -> Result<String> {
+    if let Some(limit) = options.max_file_size {
+        let size = fs::metadata(file_name)?.len();
+        if size > limit {
+            return Err(Error::FileTooLarge {
+                path: file_name.to_path_buf(),
+                size,
+                limit,
+            });
+        }
+    }
+
+    Ok(read_file_contents(file_name, options)?)
+}
+
+#[cfg(not(feature = "encoding"))]
+fn read_file_contents(file_name: &Path, _options: &LoadOptions) -> io::Result<String> {
+    fs::read_to_string(file_name)
+}
+
+#[cfg(feature = "encoding")]
+fn read_file_contents(file_name: &Path, options: &LoadOptions) -> io::Result<String> {
+    match options.encoding {
+        Some(encoding) => {
+            let bytes = fs::read(file_name)?;
+            let (file_contents, _, _) = encoding.decode(&bytes);
+            Ok(file_contents.into_owned())
+        }
+        None => fs::read_to_string(file_name),
+    }
+}
+
+/// Parses the given string as the contents of a single Terraform configuration file and
+/// interprets it as a Terraform module.
+///
+/// # Arguments
+///
+/// * `file_name` - Path of the file the contents were read from, used as the module's path and in
+///   errors
+/// * `contents` - Contents of the file
+pub fn load_module_from_str(file_name: &Path, contents: &str) -> Result<Module> {
+    let mut module = Module::new(
+        file_name
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default(),
+    );
+
+    load_file_contents(
+        file_name,
+        contents,
+        &LoadOptions::new().strict(true),
+        &mut module,
+    )?;
+    provider_config::merge_inline_provider_versions(&mut module);
+
+    Ok(module)
+}
+
+/// Parses the given string as a Terraform module consisting of a single file with an empty path.
+///
+/// This is a shorthand for [`load_module_from_str`][load_module_from_str].
+///
+/// ```
+/// # fn main() -> Result<(), tfconfig::Error> {
+/// let module = tfconfig::parse_str(
+///     r#"terraform {
+///         required_providers {
+///             aws = {
+///                 source = "hashicorp/aws"
+///             }
+///         }
+///     }"#,
+/// )?;
+///
+/// assert_eq!("hashicorp/aws", module.required_providers["aws"].source);
+/// # Ok(())
+/// # }
+/// ```
+pub fn parse_str(contents: &str) -> Result<Module> {
+    load_module_from_str(Path::new(""), contents)
+}
+
+/// Reads the directory at the given path and returns only the provider requirements of the
+/// Terraform module in it, keyed by local name.
+///
+/// This returns the same requirements as [`load_module`][load_module], including those implied by
+/// resources and `provider` blocks, but skips interpreting everything else in the module. The
+/// dependency lock file is not read either, so a malformed one cannot fail the load.
+///
+/// # Arguments
+///
+/// * `path` - Path to the directory containing the Terraform configuration
+/// * `strict` - Whether to immediately return an error if a file in the directory cannot be parsed
+pub fn load_provider_requirements(
+    path: &Path,
+    strict: bool,
+) -> Result<HashMap<String, ProviderRequirement>> {
+    let options = LoadOptions::new().strict(strict);
+
+    let module = load_dir(path, &options, |file_name, file_contents, module| {
+        let Some(file) = parse_file_contents(file_name, file_contents, &options, module)? else {
+            return Ok(());
+        };
+
+        for block in hcl::Body::from(file).blocks() {
+            match block.identifier() {
+                "terraform"
+                    if check_terraform_block_labels(file_name, block, &options, module)? =>
+                {
+                    handle_terraform_block(file_name, block.body(), &options, module)?
                 }
+                "resource" | "data" => {
+                    resource::handle_resource_provider(file_name, block, module)?
+                }
+                "provider" => provider_config::handle_provider_block(file_name, block, module)?,
+                _ => (),
             }
         }
+
+        Ok(())
+    })?;
+
+    Ok(module.required_providers)
+}
+
+/// Interprets the given already-parsed files as a Terraform module, as if they had been read from
+/// the paths they are paired with.
+///
+/// Override files, as determined by their paths, are applied after all other files regardless of
+/// their position in `bodies`. The module's path is the parent directory of the first file.
+///
+/// # Arguments
+///
+/// * `bodies` - Paths of the files paired with their parsed contents
+/// * `strict` - Whether to immediately return an error for a `terraform` block with labels,
+///   which is otherwise ignored with an error diagnostic. As the files are already parsed, there
+///   are no parse errors for it to affect.
+pub fn load_module_from_bodies(bodies: &[(PathBuf, hcl::Body)], strict: bool) -> Result<Module> {
+    let mut module = Module::new(
+        bodies
+            .first()
+            .and_then(|(file_name, _)| file_name.parent())
+            .map(Path::to_path_buf)
+            .unwrap_or_default(),
+    );
+    let options = LoadOptions::new().strict(strict);
+
+    let (primary, overrides): (Vec<_>, Vec<_>) = bodies
+        .iter()
+        .partition(|(file_name, _)| !is_override_file(file_name));
+
+    for (file_name, body) in primary.into_iter().chain(overrides) {
+        load_body(file_name, body.clone(), &options, &mut module)?;
     }
 
+    provider_config::merge_inline_provider_versions(&mut module);
+
     Ok(module)
 }
 
+/// Parses the given file contents and stores them in the given [`Module`][Module], skipping files
+/// that cannot be parsed unless `strict` is set.
+fn load_file_contents(
+    file_name: &Path,
+    file_contents: &str,
+    options: &LoadOptions,
+    module: &mut Module,
+) -> Result<()> {
+    match parse_file_contents(file_name, file_contents, options, module)? {
+        Some(file) => load_body(file_name, file.into(), options, module),
+        None => Ok(()),
+    }
+}
+
+/// Parses the given file contents, returning `None` if they cannot be parsed and `strict` is not
+/// set, in which case an error diagnostic is recorded on the module instead.
+///
+/// Files in the JSON configuration syntax, named `*.tf.json`, cannot be parsed yet. They are
+/// always skipped with an error diagnostic, even when `strict` is set, as they are valid
+/// configuration.
+fn parse_file_contents(
+    file_name: &Path,
+    file_contents: &str,
+    options: &LoadOptions,
+    module: &mut Module,
+) -> Result<Option<hcl::edit::structure::Body>> {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::debug_span!("parse_file", file = %file_name.display()).entered();
+    #[cfg(feature = "tracing")]
+    let start = std::time::Instant::now();
+
+    if is_json_config_file(file_name) {
+        #[cfg(feature = "tracing")]
+        tracing::warn!("skipped file: JSON syntax is not supported");
+
+        module.diagnostics.push(Diagnostic::new(
+            DiagnosticSeverity::Error,
+            "JSON syntax not supported".to_string(),
+            format!(
+                "{} uses the JSON configuration syntax, which is not supported yet. The file was ignored.",
+                file_name.display()
+            ),
+        ));
+        return Ok(None);
+    }
+
+    match file_contents.parse::<hcl::edit::structure::Body>() {
+        Ok(file) => {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(duration = ?start.elapsed(), "parsed file");
+
+            Ok(Some(file))
+        }
+        Err(e) => {
+            if options.strict {
+                return Err(Error::Parse(hcl::Error::Parse(e)));
+            }
+
+            #[cfg(feature = "tracing")]
+            tracing::warn!(error = %e, "skipped file: cannot be parsed");
+
+            let pos = SourcePos::new(file_name.to_path_buf(), e.location().line());
+            module.diagnostics.push(
+                Diagnostic::new(
+                    DiagnosticSeverity::Error,
+                    "Failed to parse file".to_string(),
+                    e.to_string(),
+                )
+                .with_pos(pos),
+            );
+            Ok(None)
+        }
+    }
+}
+
 /// Reads given file, interprets it and stores in given [`Module`][Module]
 pub fn load_module_from_file(
     current_file: &Path,
     file: hcl::Body,
     module: &mut Module,
 ) -> Result<()> {
+    load_body(current_file, file, &LoadOptions::default(), module)
+}
+
+fn load_body(
+    current_file: &Path,
+    file: hcl::Body,
+    options: &LoadOptions,
+    module: &mut Module,
+) -> Result<()> {
+    let mut contributed = false;
     for block in file.blocks() {
         let body = block.body();
 
-        #[allow(clippy::all)]
+        if block.identifier() != "terraform" {
+            check_misplaced_terraform_settings(current_file, block, module);
+        }
+        references::record_variable_references(block, module);
+
         match block.identifier() {
-            "terraform" => handle_terraform_block(current_file, body, module)?,
-            _ => (),
+            "terraform" => {
+                if !check_terraform_block_labels(current_file, block, options, module)? {
+                    continue;
+                }
+                handle_terraform_block(current_file, body, options, module)?
+            }
+            "variable" => variable::handle_variable_block(current_file, block, module)?,
+            "output" => output::handle_output_block(current_file, block, module)?,
+            "resource" => resource::handle_resource_block(current_file, block, module)?,
+            "data" => resource::handle_data_block(current_file, block, module)?,
+            "provider" => provider_config::handle_provider_block(current_file, block, module)?,
+            "module" => module_call::handle_module_block(current_file, block, module)?,
+            "moved" => state::handle_moved_block(block, module),
+            "import" => state::handle_import_block(block, module),
+            "removed" => state::handle_removed_block(block, module),
+            _ => continue,
         }
+
+        contributed = true;
+    }
+
+    check_top_level_terraform_settings(current_file, &file, module);
+
+    if contributed && !module.source_files.iter().any(|file| file == current_file) {
+        module.source_files.push(current_file.to_path_buf());
     }
 
     Ok(())
 }
 
+/// Returns whether the given `terraform` block has no labels and so should be interpreted.
+///
+/// Terraform rejects labels on `terraform` blocks, so a labelled block is an error when loading
+/// strictly, and is otherwise ignored with an error diagnostic.
+fn check_terraform_block_labels(
+    current_file: &Path,
+    block: &hcl::Block,
+    options: &LoadOptions,
+    module: &mut Module,
+) -> Result<bool> {
+    if block.labels().is_empty() {
+        return Ok(true);
+    }
+
+    if options.strict {
+        return Err(unexpected_labels(current_file, block));
+    }
+
+    module.diagnostics.push(Diagnostic::new(
+        DiagnosticSeverity::Error,
+        "Unexpected terraform block labels".to_string(),
+        format!(
+            "A terraform block in {} has labels, which Terraform does not allow. The block was ignored.",
+            current_file.display()
+        ),
+    ));
+    Ok(false)
+}
+
+/// Records a warning diagnostic for each `required_providers` block found directly inside the
+/// given block, as it is only meaningful in a `terraform` block. It is not added to the module's
+/// requirements.
+///
+/// Attributes are not checked, as a `required_version` or `required_providers` argument is a
+/// legitimate local value or module input.
+fn check_misplaced_terraform_settings(
+    current_file: &Path,
+    block: &hcl::Block,
+    module: &mut Module,
+) {
+    let mut block_name = block.identifier().to_string();
+    for label in block.labels() {
+        block_name.push(' ');
+        block_name.push_str(label.as_str());
+    }
+    for _ in block
+        .body()
+        .blocks()
+        .filter(|inner_block| inner_block.identifier() == "required_providers")
+    {
+        module.diagnostics.push(Diagnostic::new(
+            DiagnosticSeverity::Warning,
+            "Misplaced Terraform setting".to_string(),
+            format!(
+                "The required_providers block in the {block_name:?} block in {} is ignored, as it is only valid inside a terraform block.",
+                current_file.display()
+            ),
+        ));
+    }
+}
+
+/// Records a warning diagnostic for each `required_version` or `required_providers` argument set
+/// at the top level of a file rather than inside a `terraform` block. They are not added to the
+/// module's requirements.
+fn check_top_level_terraform_settings(current_file: &Path, file: &hcl::Body, module: &mut Module) {
+    for attr in file
+        .attributes()
+        .filter(|attr| ["required_version", "required_providers"].contains(&attr.key()))
+    {
+        module.diagnostics.push(Diagnostic::new(
+            DiagnosticSeverity::Warning,
+            "Misplaced Terraform setting".to_string(),
+            format!(
+                "The {} argument at the top level of {} is ignored, as it is only valid inside a terraform block.",
+                attr.key(),
+                current_file.display()
+            ),
+        ));
+    }
+}
+
 fn handle_terraform_block(
     current_file: &Path,
     body: &hcl::Body,
+    options: &LoadOptions,
     module: &mut Module,
 ) -> Result<()> {
-    body.attributes()
-        .filter(|attr| attr.key() == "required_version")
-        .for_each(|attr| {
-            module
-                .required_core
-                .push(attr.expr().to_string().replace('"', ""))
-        });
+    for attr in body.attributes() {
+        match attr.key() {
+            "required_version" => {
+                let constraint = attr.expr().to_string().replace('"', "");
+                if !module.required_core.contains(&constraint) {
+                    module.required_core.push(constraint);
+                    module
+                        .required_core_origins
+                        .push(current_file.to_path_buf());
+                }
+            }
+            key => {
+                module
+                    .terraform_settings
+                    .insert(key.to_string(), attr.expr().clone());
+            }
+        }
+    }
 
+    let mut seen_required_providers = false;
     for inner_block in body.blocks() {
-        #[allow(clippy::all)]
         match inner_block.identifier() {
             "required_providers" => {
-                handle_required_providers_block(current_file, inner_block.body(), module)?
+                // Terraform rejects this, but the requirements are still merged as if the blocks
+                // were in separate `terraform` blocks.
+                if seen_required_providers {
+                    module.diagnostics.push(Diagnostic::new(
+                        DiagnosticSeverity::Error,
+                        "Duplicate required providers configuration".to_string(),
+                        format!(
+                            "A terraform block in {} has more than one required_providers block.",
+                            current_file.display()
+                        ),
+                    ));
+                }
+                seen_required_providers = true;
+
+                handle_required_providers_block(current_file, inner_block.body(), options, module)?
             }
-            _ => (),
+            "backend" => backend::handle_backend_block(current_file, inner_block, module)?,
+            "encryption" => encryption::handle_encryption_block(current_file, inner_block, module)?,
+            _ => module.terraform_nested_blocks.push(inner_block.clone()),
         }
     }
 
@@ -158,22 +1459,35 @@ fn handle_terraform_block(
 fn handle_required_providers_block(
     current_file: &Path,
     required_providers: &hcl::Body,
+    options: &LoadOptions,
     module: &mut Module,
 ) -> Result<()> {
     for provider in required_providers.attributes() {
-        let provider_name = provider.key().to_string();
-        let mut provider_req = ProviderRequirement::default();
+        let provider_name = unquote(provider.key()).to_string();
+        let mut provider_req = ProviderRequirement::implied(&provider_name);
+        // Without a `source`, Terraform assumes `hashicorp/<name>`, so only a `source` that is
+        // present can be malformed.
+        let mut has_source = false;
 
         match provider.expr() {
             hcl::Expression::Object(attr) => {
                 if let Some(source) = attr.get(&ObjectKey::Identifier("source".into())) {
-                    provider_req.source = source.to_string().replace('"', "");
+                    (provider_req.source, provider_req.source_is_computed) = get_source(source);
+                    has_source = true;
                 }
                 if let Some(version) = attr.get(&ObjectKey::Identifier("version".into())) {
-                    provider_req
-                        .version_constraints
-                        .push(version.to_string().replace('"', ""));
+                    push_version_constraints_expr(&mut provider_req.version_constraints, version);
                 }
+                if let Some(aliases) =
+                    attr.get(&ObjectKey::Identifier("configuration_aliases".into()))
+                {
+                    provider_req.configuration_aliases =
+                        parse_configuration_aliases(current_file, &provider_name, aliases)?;
+                }
+            }
+            // Terraform 0.12 and earlier accepted a bare version constraint string.
+            hcl::Expression::String(version) if version::is_valid_constraints(version) => {
+                push_version_constraints(&mut provider_req.version_constraints, version);
             }
             _ => {
                 return Err(Error::UnexpectedExpr {
@@ -184,15 +1498,290 @@ fn handle_required_providers_block(
             }
         };
 
-        module
-            .required_providers
-            .insert(provider_name, provider_req);
+        if has_source && !provider_req.source_is_computed && provider_req.source_parts().is_none() {
+            if options.strict_providers {
+                return Err(Error::InvalidProviderSource {
+                    name: provider_name,
+                    source_addr: provider_req.source,
+                });
+            }
+
+            module.diagnostics.push(Diagnostic::new(
+                DiagnosticSeverity::Warning,
+                "Invalid provider source".to_string(),
+                format!(
+                    "The source {:?} for provider {provider_name:?} in {} is not a valid provider source address.",
+                    provider_req.source,
+                    current_file.display()
+                ),
+            ));
+        }
+
+        merge_provider_requirement(current_file, provider_name, provider_req, module);
+    }
+
+    if let Some(block) = required_providers.blocks().next() {
+        return Err(Error::UnexpectedBlock {
+            block_type: block.identifier().to_string(),
+            parent: "required_providers".to_string(),
+            file_name: current_file.to_path_buf(),
+        });
     }
 
     Ok(())
 }
 
-fn get_files_in_dir(path: &Path) -> Result<Vec<PathBuf>> {
+/// Stores a `required_providers` entry in the module.
+///
+/// An entry in an override file replaces any existing requirement for the provider. Otherwise, the
+/// version constraints and configuration aliases of a provider declared in several files are
+/// combined, and an error diagnostic is recorded if the sources differ.
+fn merge_provider_requirement(
+    current_file: &Path,
+    provider_name: String,
+    provider_req: ProviderRequirement,
+    module: &mut Module,
+) {
+    let existing = match module.required_providers.get_mut(&provider_name) {
+        Some(existing) if !is_override_file(current_file) => existing,
+        Some(_) => {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(
+                provider = %provider_name,
+                file = %current_file.display(),
+                "override file replaced provider requirement"
+            );
+
+            module
+                .required_providers
+                .insert(provider_name, provider_req);
+            return;
+        }
+        None => {
+            module
+                .required_providers
+                .insert(provider_name, provider_req);
+            return;
+        }
+    };
+
+    if existing.source.is_empty() {
+        existing.source = provider_req.source;
+        existing.source_is_computed = provider_req.source_is_computed;
+    } else if !provider_req.source.is_empty()
+        && effective_source(&provider_name, existing)
+            != effective_source(&provider_name, &provider_req)
+    {
+        let conflicting_source = effective_source(&provider_name, &provider_req);
+        if !existing.conflicting_sources.contains(&conflicting_source) {
+            existing.conflicting_sources.push(conflicting_source);
+        }
+        module.diagnostics.push(Diagnostic::new(
+            DiagnosticSeverity::Error,
+            "Conflicting provider source".to_string(),
+            format!(
+                "Provider {provider_name:?} is required with source {:?} in {}, but was already required with source {:?}.",
+                provider_req.source,
+                current_file.display(),
+                existing.source
+            ),
+        ));
+    }
+
+    for constraint in &provider_req.version_constraints {
+        push_version_constraints(&mut existing.version_constraints, constraint);
+    }
+    for alias in provider_req.configuration_aliases {
+        if !existing.configuration_aliases.contains(&alias) {
+            existing.configuration_aliases.push(alias);
+        }
+    }
+}
+
+/// Strips the surrounding quotes of a `required_providers` key given as a string, such as from a
+/// generated or programmatically built body.
+fn unquote(key: &str) -> &str {
+    key.strip_prefix('"')
+        .and_then(|key| key.strip_suffix('"'))
+        .unwrap_or(key)
+}
+
+/// Returns the `source` of a `required_providers` entry and whether it is computed rather than a
+/// static string, in which case the raw template or expression is returned.
+fn get_source(source: &hcl::Expression) -> (String, bool) {
+    match source {
+        hcl::Expression::String(source) => (source.clone(), false),
+        hcl::Expression::TemplateExpr(template) => match template.as_ref() {
+            hcl::TemplateExpr::QuotedString(template) => (template.clone(), true),
+            hcl::TemplateExpr::Heredoc(heredoc) => (heredoc.template.clone(), true),
+        },
+        source => (source.to_string(), true),
+    }
+}
+
+/// Records a requirement for a provider that is only referenced implicitly, such as through a
+/// resource type or `provider` block, unless the provider is already declared in
+/// `required_providers`.
+fn add_implied_provider_requirement(provider_name: &str, module: &mut Module) {
+    if !module.required_providers.contains_key(provider_name) {
+        module.required_providers.insert(
+            provider_name.to_string(),
+            ProviderRequirement::implied(provider_name),
+        );
+    }
+}
+
+/// Returns the only label of the given block, which is used as its name.
+fn get_block_name(current_file: &Path, block: &hcl::Block) -> Result<String> {
+    match block.labels() {
+        [name] => Ok(name.as_str().to_string()),
+        _ => Err(unexpected_labels(current_file, block)),
+    }
+}
+
+fn unexpected_labels(current_file: &Path, block: &hcl::Block) -> Error {
+    Error::UnexpectedLabels {
+        block_type: block.identifier().to_string(),
+        labels: block
+            .labels()
+            .iter()
+            .map(|l| l.as_str().to_string())
+            .collect(),
+        file_name: current_file.to_path_buf(),
+    }
+}
+
+/// Splits a comma-separated version constraint string into its individual constraints, appending
+/// them in source order and skipping any that are already present.
+fn push_version_constraints(constraints: &mut Vec<String>, version: &str) {
+    for constraint in version.split(',').map(str::trim) {
+        if !constraint.is_empty() && !constraints.iter().any(|c| c == constraint) {
+            constraints.push(constraint.to_string());
+        }
+    }
+}
+
+/// Adds the constraints of a `version` expression, which may be a comma-separated string or an
+/// array of them, such as `[">= 1.0, < 2.0", "!= 1.5.0"]`.
+fn push_version_constraints_expr(constraints: &mut Vec<String>, version: &hcl::Expression) {
+    match version {
+        hcl::Expression::Array(versions) => {
+            for version in versions {
+                push_version_constraints_expr(constraints, version);
+            }
+        }
+        hcl::Expression::String(version) => push_version_constraints(constraints, version),
+        version => push_version_constraints(constraints, &version.to_string().replace('"', "")),
+    }
+}
+
+fn parse_configuration_aliases(
+    current_file: &Path,
+    provider_name: &str,
+    aliases: &hcl::Expression,
+) -> Result<Vec<ProviderRef>> {
+    let unexpected_expr = || Error::UnexpectedExpr {
+        attribute_key: format!("{provider_name}.configuration_aliases"),
+        expr: aliases.clone(),
+        file_name: current_file.to_path_buf(),
+    };
+
+    let hcl::Expression::Array(aliases) = aliases else {
+        return Err(unexpected_expr());
+    };
+
+    aliases
+        .iter()
+        .map(|alias| parse_provider_ref(alias).ok_or_else(unexpected_expr))
+        .collect()
+}
+
+/// Parses a provider reference of the form `name` or `name.alias`.
+fn parse_provider_ref(expr: &hcl::Expression) -> Option<ProviderRef> {
+    match expr {
+        hcl::Expression::Variable(name) => Some(ProviderRef::new(name.to_string(), String::new())),
+        hcl::Expression::Traversal(traversal) => {
+            let hcl::Expression::Variable(name) = &traversal.expr else {
+                return None;
+            };
+
+            match traversal.operators.as_slice() {
+                [hcl::TraversalOperator::GetAttr(alias)] => {
+                    Some(ProviderRef::new(name.to_string(), alias.to_string()))
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Returns the declaration that a block in the given file is merged into, removing it from the
+/// map so that it can be updated and inserted again.
+///
+/// As in Terraform, a block in an override file is merged into the existing declaration argument
+/// by argument, rather than replacing it, so only an override file returns a declaration.
+fn take_overridden<T>(
+    current_file: &Path,
+    declarations: &mut HashMap<String, T>,
+    key: &str,
+) -> Option<T> {
+    if is_override_file(current_file) {
+        declarations.remove(key)
+    } else {
+        None
+    }
+}
+
+/// Parses the `depends_on` meta-argument of the given body into the addresses it references, such
+/// as `aws_instance.web` or `module.network`, returning `None` if it is not set.
+fn get_depends_on(current_file: &Path, body: &hcl::Body) -> Result<Option<Vec<String>>> {
+    let Some(attr) = body.attributes().find(|attr| attr.key() == "depends_on") else {
+        return Ok(None);
+    };
+
+    let unexpected_expr = || Error::UnexpectedExpr {
+        attribute_key: attr.key().to_string(),
+        expr: attr.expr().clone(),
+        file_name: current_file.to_path_buf(),
+    };
+
+    let hcl::Expression::Array(elements) = attr.expr() else {
+        return Err(unexpected_expr());
+    };
+
+    elements
+        .iter()
+        .map(|element| parse_reference(element).ok_or_else(unexpected_expr))
+        .collect::<Result<_>>()
+        .map(Some)
+}
+
+/// Parses a static reference to an object, such as `aws_instance.web`, into its dotted address.
+fn parse_reference(expr: &hcl::Expression) -> Option<String> {
+    match expr {
+        hcl::Expression::Variable(name) => Some(name.to_string()),
+        hcl::Expression::Traversal(traversal) => {
+            let hcl::Expression::Variable(name) = &traversal.expr else {
+                return None;
+            };
+
+            let mut reference = name.to_string();
+            for operator in &traversal.operators {
+                let hcl::TraversalOperator::GetAttr(attr) = operator else {
+                    return None;
+                };
+                reference.push('.');
+                reference.push_str(attr.as_str());
+            }
+
+            Some(reference)
+        }
+        _ => None,
+    }
+}
+
+fn get_files_in_dir(path: &Path, options: &LoadOptions) -> Result<Vec<PathBuf>> {
     let mut primary = vec![];
     let mut overrides = vec![];
 
@@ -202,31 +1791,26 @@ fn get_files_in_dir(path: &Path) -> Result<Vec<PathBuf>> {
             continue;
         }
 
-        match file.extension() {
-            Some(ext) => {
-                match ext.to_str() {
-                    Some(ext) => {
-                        if ext.starts_with('.')
-                            || ext.starts_with('#')
-                            || ext.ends_with('~')
-                            || ext.ends_with('#')
-                        {
-                            continue;
-                        }
-                    }
-                    None => continue,
-                };
-            }
+        let file_name = match file.file_name() {
+            Some(file_name) => match file_name.to_str() {
+                Some(file_name) => file_name,
+                None if options.strict => return Err(Error::NonUtf8Path(file)),
+                None => {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(file = %file.display(), "skipped file: name is not valid UTF-8");
+                    continue;
+                }
+            },
             None => continue,
         };
 
-        let basename = match file.file_stem() {
-            Some(basename) => basename.to_str().unwrap(),
-            None => continue,
-        };
-        let is_override = basename == "override" || basename.ends_with("_override");
+        if !is_config_file_name(file_name, options.include_hidden) {
+            #[cfg(feature = "tracing")]
+            tracing::trace!(file = %file.display(), "skipped file: not a configuration file");
+            continue;
+        }
 
-        if is_override {
+        if is_override_basename(config_basename(file_name)) {
             overrides.push(file);
         } else {
             primary.push(file);
@@ -236,3 +1820,44 @@ fn get_files_in_dir(path: &Path) -> Result<Vec<PathBuf>> {
     primary.append(&mut overrides);
     Ok(primary)
 }
+
+/// Returns whether the given file name is that of a Terraform configuration file, skipping
+/// hidden files unless `include_hidden` is set, as well as editor backup and autosave files.
+fn is_config_file_name(file_name: &str, include_hidden: bool) -> bool {
+    if file_name.starts_with('.') && !include_hidden {
+        return false;
+    }
+    if file_name.ends_with('~') || (file_name.starts_with('#') && file_name.ends_with('#')) {
+        return false;
+    }
+
+    file_name.ends_with(".tf") || file_name.ends_with(".tf.json")
+}
+
+fn is_json_config_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|file_name| file_name.to_str())
+        .is_some_and(|file_name| file_name.ends_with(".tf.json"))
+}
+
+/// Returns whether the given file is an override file, whose contents take precedence over the
+/// module's primary files.
+pub fn is_override_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|file_name| file_name.to_str())
+        .map(config_basename)
+        .is_some_and(is_override_basename)
+}
+
+/// Strips the `.tf.json` or `.tf` extension from the given file name. Unlike
+/// [`Path::file_stem`], this removes both extensions of `main.tf.json`.
+fn config_basename(file_name: &str) -> &str {
+    file_name
+        .strip_suffix(".tf.json")
+        .or_else(|| file_name.strip_suffix(".tf"))
+        .unwrap_or(file_name)
+}
+
+fn is_override_basename(basename: &str) -> bool {
+    basename == "override" || basename.ends_with("_override")
+}
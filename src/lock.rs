@@ -0,0 +1,107 @@
+use crate::{
+    get_block_name, handle_read_file_error, parse_file_contents, push_version_constraints,
+    read_file, Diagnostic, DiagnosticSeverity, Error, LoadOptions, Module, Result, SourceAddr,
+};
+use std::{collections::HashMap, io, path::Path};
+
+/// Name of the dependency lock file Terraform writes next to a module's configuration.
+pub const LOCK_FILE_NAME: &str = ".terraform.lock.hcl";
+
+/// The providers recorded in a module's dependency lock file.
+#[derive(Debug, Default, Clone)]
+pub struct LockFile {
+    /// Locked providers, keyed by their source address as written in the lock file, such as
+    /// `registry.terraform.io/hashicorp/aws`.
+    pub providers: HashMap<String, LockedProvider>,
+}
+
+impl LockFile {
+    /// Returns the locked provider with the given source address. The default registry hostname
+    /// is filled in if the address does not include one, so `hashicorp/aws` finds
+    /// `registry.terraform.io/hashicorp/aws`.
+    pub fn provider(&self, source: &str) -> Option<&LockedProvider> {
+        match SourceAddr::parse(source) {
+            Some(addr) => self.providers.get(&addr.to_string()),
+            None => self.providers.get(source),
+        }
+    }
+}
+
+/// A `provider` block in a dependency lock file.
+#[derive(Debug, Default, Clone)]
+pub struct LockedProvider {
+    /// The version Terraform selected for the provider.
+    pub version: String,
+    /// The version constraints the provider was selected against.
+    pub constraints: Vec<String>,
+    /// Checksums of the provider's packages, such as `h1:...` or `zh:...`.
+    pub hashes: Vec<String>,
+}
+
+/// Reads the dependency lock file in the module's directory into
+/// [`Module::lock_file`][Module::lock_file], if there is one.
+///
+/// Unless loading strictly, a lock file that is too large or cannot be parsed is skipped with an
+/// error diagnostic, as is a `provider` block in it without a single label, so that it cannot
+/// fail the load of an otherwise valid module.
+pub(crate) fn load_lock_file(
+    path: &Path,
+    options: &LoadOptions,
+    module: &mut Module,
+) -> Result<()> {
+    let file_name = path.join(LOCK_FILE_NAME);
+    let contents = match read_file(&file_name, options) {
+        Ok(contents) => contents,
+        Err(Error::Io(e)) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return handle_read_file_error(&file_name, e, options, module),
+    };
+
+    let Some(body) = parse_file_contents(&file_name, &contents, options, module)? else {
+        return Ok(());
+    };
+
+    let mut lock_file = LockFile::default();
+    for block in hcl::Body::from(body).blocks() {
+        if block.identifier() != "provider" {
+            continue;
+        }
+
+        let source = match get_block_name(&file_name, block) {
+            Ok(source) => source,
+            Err(e) if options.strict => return Err(e),
+            Err(e) => {
+                module.diagnostics.push(Diagnostic::new(
+                    DiagnosticSeverity::Error,
+                    "Invalid lock file entry".to_string(),
+                    format!("{e}. The block was ignored."),
+                ));
+                continue;
+            }
+        };
+        let mut provider = LockedProvider::default();
+        for attr in block.body().attributes() {
+            match (attr.key(), attr.expr()) {
+                ("version", hcl::Expression::String(version)) => provider.version = version.clone(),
+                ("constraints", hcl::Expression::String(constraints)) => {
+                    push_version_constraints(&mut provider.constraints, constraints)
+                }
+                ("hashes", hcl::Expression::Array(hashes)) => {
+                    provider.hashes = hashes
+                        .iter()
+                        .filter_map(|hash| match hash {
+                            hcl::Expression::String(hash) => Some(hash.clone()),
+                            _ => None,
+                        })
+                        .collect()
+                }
+                _ => (),
+            }
+        }
+
+        lock_file.providers.insert(source, provider);
+    }
+
+    module.lock_file = Some(lock_file);
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use crate::{get_block_name, get_depends_on, take_overridden, Module, Result};
+use std::path::{Path, PathBuf};
+
+/// A `module` block calling another module.
+#[derive(Debug, Default, Clone)]
+pub struct ModuleCall {
+    pub name: String,
+    pub source: String,
+    pub version: String,
+    /// Addresses of the objects listed in the `depends_on` meta-argument.
+    pub depends_on: Vec<String>,
+    /// The `count` meta-argument, if set.
+    pub count: Option<hcl::Expression>,
+    /// The `for_each` meta-argument, if set.
+    pub for_each: Option<hcl::Expression>,
+}
+
+impl ModuleCall {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            ..Default::default()
+        }
+    }
+
+    /// Returns whether the module is sourced from a local path rather than a registry or remote
+    /// location.
+    pub fn is_local(&self) -> bool {
+        ["./", "../", ".\\", "..\\"]
+            .iter()
+            .any(|prefix| self.source.starts_with(prefix))
+    }
+
+    /// Returns whether the module is called with `count` or `for_each`, and so may have any
+    /// number of instances.
+    pub fn is_repeated(&self) -> bool {
+        self.count.is_some() || self.for_each.is_some()
+    }
+
+    /// Resolves a local module source against the directory of the calling module, returning the
+    /// canonicalized path.
+    ///
+    /// Returns `None` if the source is not local, or if the resolved path does not exist.
+    pub fn resolved_local_path(&self, base: &Path) -> Option<PathBuf> {
+        if !self.is_local() {
+            return None;
+        }
+
+        base.join(&self.source).canonicalize().ok()
+    }
+}
+
+pub(crate) fn handle_module_block(
+    current_file: &Path,
+    block: &hcl::Block,
+    module: &mut Module,
+) -> Result<()> {
+    let name = get_block_name(current_file, block)?;
+
+    let mut module_call = take_overridden(current_file, &mut module.module_calls, &name)
+        .unwrap_or_else(|| ModuleCall::new(name));
+    for attr in block.body().attributes() {
+        match attr.key() {
+            "source" => module_call.source = attr.expr().to_string().replace('"', ""),
+            "version" => module_call.version = attr.expr().to_string().replace('"', ""),
+            "count" => module_call.count = Some(attr.expr().clone()),
+            "for_each" => module_call.for_each = Some(attr.expr().clone()),
+            _ => (),
+        }
+    }
+    if let Some(depends_on) = get_depends_on(current_file, block.body())? {
+        module_call.depends_on = depends_on;
+    }
+
+    module
+        .module_calls
+        .insert(module_call.name.clone(), module_call);
+
+    Ok(())
+}
@@ -0,0 +1,188 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// What to do with a file in the module that cannot be read, as set by
+/// [`LoadOptions::on_read_error`][LoadOptions::on_read_error].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadErrorPolicy {
+    /// Skip the file.
+    Skip,
+    /// Skip the file and record an error diagnostic.
+    Diagnose,
+    /// Return an [`Error::Io`][crate::Error::Io].
+    Fail,
+}
+
+/// A function transforming a file's contents before it is parsed, as set by
+/// [`LoadOptions::preprocess`][LoadOptions::preprocess].
+type PreprocessFn = dyn Fn(&Path, String) -> String + Send + Sync;
+
+#[derive(Clone)]
+pub(crate) struct Preprocessor(Arc<PreprocessFn>);
+
+impl fmt::Debug for Preprocessor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Preprocessor")
+    }
+}
+
+/// Options controlling how a module is loaded by
+/// [`load_module_with_options`][crate::load_module_with_options].
+#[derive(Debug, Clone, Default)]
+pub struct LoadOptions {
+    pub(crate) strict: bool,
+    pub(crate) strict_providers: bool,
+    pub(crate) include_hidden: bool,
+    pub(crate) canonicalize_path: bool,
+    pub(crate) load_variable_values: bool,
+    pub(crate) load_lock_file: bool,
+    pub(crate) max_depth: Option<usize>,
+    pub(crate) read_threads: usize,
+    pub(crate) max_file_size: Option<u64>,
+    pub(crate) max_diagnostics: Option<usize>,
+    pub(crate) on_read_error: Option<ReadErrorPolicy>,
+    pub(crate) dev_overrides: HashMap<String, PathBuf>,
+    pub(crate) preprocess: Option<Preprocessor>,
+    #[cfg(feature = "encoding")]
+    pub(crate) encoding: Option<&'static encoding_rs::Encoding>,
+}
+
+impl LoadOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether to immediately return an error if a file in the module cannot be read or
+    /// parsed. Otherwise, the file is skipped. Defaults to `false`.
+    ///
+    /// Files that cannot be read can be handled differently with
+    /// [`on_read_error`][LoadOptions::on_read_error].
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Sets whether to return an [`Error::InvalidProviderSource`][crate::Error::InvalidProviderSource]
+    /// for a `required_providers` entry with a malformed `source`. Otherwise, a warning diagnostic
+    /// is recorded. An entry without a `source` is valid, as Terraform then assumes
+    /// `hashicorp/<name>`. Defaults to `false`.
+    pub fn strict_providers(mut self, strict_providers: bool) -> Self {
+        self.strict_providers = strict_providers;
+        self
+    }
+
+    /// Sets whether to load configuration files whose name starts with a dot, such as
+    /// `.generated.tf`. Terraform ignores these files. Defaults to `false`.
+    pub fn include_hidden(mut self, include_hidden: bool) -> Self {
+        self.include_hidden = include_hidden;
+        self
+    }
+
+    /// Sets whether to store the canonical, absolute form of the module's path in
+    /// [`Module::path`][crate::Module::path]. The path is kept as given if it cannot be
+    /// canonicalized. Defaults to `false`.
+    pub fn canonicalize_path(mut self, canonicalize_path: bool) -> Self {
+        self.canonicalize_path = canonicalize_path;
+        self
+    }
+
+    /// Sets whether to read the variable definitions files Terraform loads automatically,
+    /// `terraform.tfvars` and `*.auto.tfvars`, along with their `.json` forms, into
+    /// [`Module::variable_values`][crate::Module::variable_values]. Defaults to `false`.
+    pub fn load_variable_values(mut self, load_variable_values: bool) -> Self {
+        self.load_variable_values = load_variable_values;
+        self
+    }
+
+    /// Sets whether to read the dependency lock file, `.terraform.lock.hcl`, into
+    /// [`Module::lock_file`][crate::Module::lock_file]. A lock file that cannot be read or parsed is
+    /// handled like any other file in the module. Defaults to `false`.
+    pub fn load_lock_file(mut self, load_lock_file: bool) -> Self {
+        self.load_lock_file = load_lock_file;
+        self
+    }
+
+    /// Sets how many levels of module calls [`load_module_tree`][crate::load_module_tree]
+    /// descends into, where `0` loads only the root module. Defaults to no limit.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Sets how many threads files are read on at once, which can hide IO latency on slow
+    /// filesystems. Files are still parsed in order on the calling thread, so the loaded module is
+    /// the same either way, but the contents of every file are held in memory until they have all
+    /// been read. Defaults to reading on the calling thread only, one file at a time.
+    pub fn read_threads(mut self, read_threads: usize) -> Self {
+        self.read_threads = read_threads;
+        self
+    }
+
+    /// Sets the largest file, in bytes, that is read. A larger file is skipped with an error
+    /// diagnostic, or fails the load with an [`Error::FileTooLarge`][crate::Error::FileTooLarge]
+    /// when loading strictly. Defaults to no limit.
+    pub fn max_file_size(mut self, max_file_size: u64) -> Self {
+        self.max_file_size = Some(max_file_size);
+        self
+    }
+
+    /// Sets how many diagnostics are kept when loading a module. Any further diagnostics are
+    /// dropped, and a final warning diagnostic records how many were suppressed. Defaults to no
+    /// limit.
+    pub fn max_diagnostics(mut self, max_diagnostics: usize) -> Self {
+        self.max_diagnostics = Some(max_diagnostics);
+        self
+    }
+
+    /// Sets what to do with a file that cannot be read, such as one that is not valid UTF-8,
+    /// regardless of [`strict`][LoadOptions::strict]. Defaults to
+    /// [`ReadErrorPolicy::Fail`][ReadErrorPolicy::Fail] when loading strictly and
+    /// [`ReadErrorPolicy::Skip`][ReadErrorPolicy::Skip] otherwise.
+    pub fn on_read_error(mut self, on_read_error: ReadErrorPolicy) -> Self {
+        self.on_read_error = Some(on_read_error);
+        self
+    }
+
+    pub(crate) fn read_error_policy(&self) -> ReadErrorPolicy {
+        match self.on_read_error {
+            Some(on_read_error) => on_read_error,
+            None if self.strict => ReadErrorPolicy::Fail,
+            None => ReadErrorPolicy::Skip,
+        }
+    }
+
+    /// Sets the `dev_overrides` of the Terraform CLI configuration, mapping provider source
+    /// addresses to the local directories the providers are loaded from instead. Each matching
+    /// [`ProviderRequirement::dev_override`][crate::ProviderRequirement::dev_override] is set
+    /// accordingly. Defaults to no overrides.
+    pub fn dev_overrides(mut self, dev_overrides: HashMap<String, PathBuf>) -> Self {
+        self.dev_overrides = dev_overrides;
+        self
+    }
+
+    /// Sets a function that is called with the path and contents of each configuration file, and
+    /// returns the contents to parse instead. This allows near-HCL formats, such as files with
+    /// custom templating markers, to be loaded. Defaults to parsing files as they are.
+    pub fn preprocess(mut self, preprocess: Box<PreprocessFn>) -> Self {
+        self.preprocess = Some(Preprocessor(Arc::from(preprocess)));
+        self
+    }
+
+    pub(crate) fn preprocessed(&self, file_name: &Path, contents: String) -> String {
+        match &self.preprocess {
+            Some(Preprocessor(preprocess)) => preprocess(file_name, contents),
+            None => contents,
+        }
+    }
+
+    /// Sets the character encoding files are decoded from before being parsed. Defaults to UTF-8.
+    #[cfg(feature = "encoding")]
+    pub fn encoding(mut self, encoding: &'static encoding_rs::Encoding) -> Self {
+        self.encoding = Some(encoding);
+        self
+    }
+}
@@ -0,0 +1,56 @@
+use crate::{get_block_name, references::collect_references, take_overridden, Module, Result};
+use std::path::Path;
+
+/// An `output` block declared in the module.
+#[derive(Debug, Default, Clone)]
+pub struct Output {
+    pub name: String,
+    pub description: String,
+    pub sensitive: bool,
+    /// The `value` expression, if set.
+    pub value: Option<hcl::Expression>,
+}
+
+impl Output {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the addresses referenced by the output's value, such as `var.name` or
+    /// `aws_instance.web.public_ip`, in source order and without duplicates.
+    pub fn referenced_addresses(&self) -> Vec<String> {
+        let mut references = vec![];
+        if let Some(value) = &self.value {
+            collect_references(value, &mut references);
+        }
+        references
+    }
+}
+
+pub(crate) fn handle_output_block(
+    current_file: &Path,
+    block: &hcl::Block,
+    module: &mut Module,
+) -> Result<()> {
+    let name = get_block_name(current_file, block)?;
+
+    let mut output = take_overridden(current_file, &mut module.outputs, &name)
+        .unwrap_or_else(|| Output::new(name));
+    for attr in block.body().attributes() {
+        match (attr.key(), attr.expr()) {
+            ("description", hcl::Expression::String(description)) => {
+                output.description = description.clone()
+            }
+            ("sensitive", hcl::Expression::Bool(sensitive)) => output.sensitive = *sensitive,
+            ("value", value) => output.value = Some(value.clone()),
+            _ => (),
+        }
+    }
+
+    module.outputs.insert(output.name.clone(), output);
+
+    Ok(())
+}
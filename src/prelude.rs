@@ -0,0 +1,35 @@
+//! Re-exports of the most commonly used types and functions.
+//!
+//! ```
+//! use std::path::Path;
+//! use tfconfig::prelude::*;
+//!
+//! # fn main() -> Result<(), Error> {
+//! let module: Module = load_module_from_str(
+//!     Path::new("versions.tf"),
+//!     r#"terraform {
+//!         required_providers {
+//!             aws = {
+//!                 source  = "hashicorp/aws"
+//!                 version = "~> 5.0"
+//!             }
+//!         }
+//!     }"#,
+//! )?;
+//!
+//! let aws: &ProviderRequirement = &module.required_providers["aws"];
+//! assert_eq!("hashicorp/aws", aws.source);
+//! # Ok(())
+//! # }
+//! ```
+
+pub use crate::{
+    load_module, load_module_from_bodies, load_module_from_file, load_module_from_str,
+    load_module_rich, load_module_tree, load_module_with_options, load_provider_requirements,
+    parse_str, Backend, DataResource, Diagnostic, DiagnosticSeverity, Error, IgnoreChanges,
+    LoadOptions, ManagedResource, Module, ModuleCall, ModuleSummary, ModuleTree, Output,
+    ProviderConfig, ProviderRef, ProviderRequirement, ReadErrorPolicy, RichModule, VarType,
+    Variable,
+};
+#[cfg(feature = "archive")]
+pub use crate::{load_module_from_archive, load_module_from_archive_with_options, ArchiveFormat};
@@ -0,0 +1,119 @@
+use crate::{
+    add_implied_provider_requirement, get_block_name, push_version_constraints,
+    resource::merge_nested_blocks, take_overridden, Diagnostic, DiagnosticSeverity, Module, Result,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+};
+
+/// A `provider` block configuring a provider.
+#[derive(Debug, Default, Clone)]
+pub struct ProviderConfig {
+    pub name: String,
+    pub alias: String,
+    /// The deprecated inline `version` constraint, if any.
+    pub version: Option<String>,
+    /// Arguments set in the configuration, keyed by name, excluding `alias` and `version`.
+    pub attributes: HashMap<String, hcl::Expression>,
+    /// Blocks nested in the configuration, such as `assume_role` or `endpoints`, paired with their
+    /// block type, in source order.
+    pub nested_blocks: Vec<(String, hcl::Body)>,
+}
+
+impl ProviderConfig {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the key the configuration is stored under, which is `name` or `name.alias`.
+    pub fn key(&self) -> String {
+        if self.alias.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{}.{}", self.name, self.alias)
+        }
+    }
+}
+
+pub(crate) fn handle_provider_block(
+    current_file: &Path,
+    block: &hcl::Block,
+    module: &mut Module,
+) -> Result<()> {
+    let name = get_block_name(current_file, block)?;
+
+    let alias = block
+        .body()
+        .attributes()
+        .find(|attr| attr.key() == "alias")
+        .map(|attr| attr.expr().to_string().replace('"', ""))
+        .unwrap_or_default();
+    let key = ProviderConfig {
+        name: name.clone(),
+        alias,
+        ..Default::default()
+    }
+    .key();
+
+    let mut provider_config = take_overridden(current_file, &mut module.provider_configs, &key)
+        .unwrap_or_else(|| ProviderConfig::new(name));
+    for attr in block.body().attributes() {
+        match attr.key() {
+            "alias" => provider_config.alias = attr.expr().to_string().replace('"', ""),
+            "version" => provider_config.version = Some(attr.expr().to_string().replace('"', "")),
+            key => {
+                provider_config
+                    .attributes
+                    .insert(key.to_string(), attr.expr().clone());
+            }
+        }
+    }
+    merge_nested_blocks(block.body(), &mut provider_config.nested_blocks);
+
+    add_implied_provider_requirement(&provider_config.name, module);
+    module
+        .provider_configs
+        .insert(provider_config.key(), provider_config);
+
+    Ok(())
+}
+
+/// Merges the deprecated inline `version` of each provider configuration into the module's
+/// provider requirements once all files are loaded.
+///
+/// As in Terraform, constraints declared in `required_providers` take precedence: inline versions
+/// are only used for providers without any, and are otherwise dropped with a warning.
+pub(crate) fn merge_inline_provider_versions(module: &mut Module) {
+    let constrained: HashSet<String> = module
+        .required_providers
+        .iter()
+        .filter(|(_, req)| !req.version_constraints.is_empty())
+        .map(|(name, _)| name.clone())
+        .collect();
+
+    let mut configs: Vec<&ProviderConfig> = module.provider_configs.values().collect();
+    configs.sort_by_key(|config| config.key());
+
+    for config in configs {
+        let Some(version) = &config.version else {
+            continue;
+        };
+
+        if constrained.contains(&config.name) {
+            module.diagnostics.push(Diagnostic::new(
+                DiagnosticSeverity::Warning,
+                "Version constraint in provider block is overridden".to_string(),
+                format!(
+                    "The version constraint \"{version}\" in the configuration for provider \"{}\" is ignored because it is also declared in required_providers.",
+                    config.key()
+                ),
+            ));
+        } else if let Some(req) = module.required_providers.get_mut(&config.name) {
+            push_version_constraints(&mut req.version_constraints, version);
+        }
+    }
+}
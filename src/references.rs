@@ -0,0 +1,164 @@
+use crate::Module;
+
+/// Collects the addresses referenced by the given expression into `references`, in source order and
+/// without duplicates.
+///
+/// A reference is a variable followed by any attribute accesses, such as `var.name` or
+/// `aws_instance.web.id`. An index or splat ends the address, although references within an index
+/// are collected too. Temporary variables declared by `for` expressions are not references.
+pub(crate) fn collect_references(expr: &hcl::Expression, references: &mut Vec<String>) {
+    collect(expr, &mut vec![], references);
+}
+
+/// Records the input variables referenced as `var.name` anywhere in the given block, including
+/// its nested blocks, in [`Module::referenced_variables`][Module::referenced_variables].
+///
+/// References a `variable` block makes to itself, such as in its `validation` blocks, are not
+/// recorded.
+pub(crate) fn record_variable_references(block: &hcl::Block, module: &mut Module) {
+    let mut references = vec![];
+    collect_body(block.body(), &mut references);
+
+    let own_name = match (block.identifier(), block.labels()) {
+        ("variable", [name]) => Some(name.as_str()),
+        _ => None,
+    };
+    for reference in &references {
+        let Some(name) = reference
+            .strip_prefix("var.")
+            .and_then(|name| name.split('.').next())
+        else {
+            continue;
+        };
+        if Some(name) != own_name {
+            module.referenced_variables.insert(name.to_string());
+        }
+    }
+}
+
+fn collect_body(body: &hcl::Body, references: &mut Vec<String>) {
+    for attr in body.attributes() {
+        collect_references(attr.expr(), references);
+    }
+    for block in body.blocks() {
+        collect_body(block.body(), references);
+    }
+}
+
+fn collect(expr: &hcl::Expression, scope: &mut Vec<String>, references: &mut Vec<String>) {
+    match expr {
+        hcl::Expression::Array(exprs) => {
+            for expr in exprs {
+                collect(expr, scope, references);
+            }
+        }
+        hcl::Expression::Object(object) => {
+            for (key, value) in object {
+                if let hcl::ObjectKey::Expression(key) = key {
+                    collect(key, scope, references);
+                }
+                collect(value, scope, references);
+            }
+        }
+        hcl::Expression::TemplateExpr(template) => {
+            if let Ok(template) = hcl::Template::from_expr(template) {
+                collect_template(&template, scope, references);
+            }
+        }
+        hcl::Expression::Variable(name) => push_reference(name.to_string(), scope, references),
+        hcl::Expression::Traversal(traversal) => {
+            let mut operators = traversal.operators.iter().peekable();
+            if let hcl::Expression::Variable(name) = &traversal.expr {
+                let mut reference = name.to_string();
+                while let Some(hcl::TraversalOperator::GetAttr(attr)) = operators.peek() {
+                    reference.push('.');
+                    reference.push_str(attr.as_str());
+                    operators.next();
+                }
+                push_reference(reference, scope, references);
+            } else {
+                collect(&traversal.expr, scope, references);
+            }
+
+            for operator in operators {
+                if let hcl::TraversalOperator::Index(index) = operator {
+                    collect(index, scope, references);
+                }
+            }
+        }
+        hcl::Expression::FuncCall(func_call) => {
+            for arg in &func_call.args {
+                collect(arg, scope, references);
+            }
+        }
+        hcl::Expression::Parenthesis(expr) => collect(expr, scope, references),
+        hcl::Expression::Conditional(conditional) => {
+            collect(&conditional.cond_expr, scope, references);
+            collect(&conditional.true_expr, scope, references);
+            collect(&conditional.false_expr, scope, references);
+        }
+        hcl::Expression::Operation(operation) => match operation.as_ref() {
+            hcl::Operation::Unary(op) => collect(&op.expr, scope, references),
+            hcl::Operation::Binary(op) => {
+                collect(&op.lhs_expr, scope, references);
+                collect(&op.rhs_expr, scope, references);
+            }
+        },
+        hcl::Expression::ForExpr(for_expr) => {
+            collect(&for_expr.collection_expr, scope, references);
+
+            let depth = scope.len();
+            scope.extend(for_expr.key_var.iter().map(ToString::to_string));
+            scope.push(for_expr.value_var.to_string());
+            if let Some(key_expr) = &for_expr.key_expr {
+                collect(key_expr, scope, references);
+            }
+            collect(&for_expr.value_expr, scope, references);
+            if let Some(cond_expr) = &for_expr.cond_expr {
+                collect(cond_expr, scope, references);
+            }
+            scope.truncate(depth);
+        }
+        _ => (),
+    }
+}
+
+fn collect_template(
+    template: &hcl::Template,
+    scope: &mut Vec<String>,
+    references: &mut Vec<String>,
+) {
+    for element in template.elements() {
+        match element {
+            hcl::template::Element::Literal(_) => (),
+            hcl::template::Element::Interpolation(interpolation) => {
+                collect(&interpolation.expr, scope, references)
+            }
+            hcl::template::Element::Directive(hcl::template::Directive::If(directive)) => {
+                collect(&directive.cond_expr, scope, references);
+                collect_template(&directive.true_template, scope, references);
+                if let Some(false_template) = &directive.false_template {
+                    collect_template(false_template, scope, references);
+                }
+            }
+            hcl::template::Element::Directive(hcl::template::Directive::For(directive)) => {
+                collect(&directive.collection_expr, scope, references);
+
+                let depth = scope.len();
+                scope.extend(directive.key_var.iter().map(ToString::to_string));
+                scope.push(directive.value_var.to_string());
+                collect_template(&directive.template, scope, references);
+                scope.truncate(depth);
+            }
+        }
+    }
+}
+
+fn push_reference(reference: String, scope: &[String], references: &mut Vec<String>) {
+    let root = reference.split('.').next().unwrap_or_default();
+    if scope.iter().any(|name| name == root) || references.contains(&reference) {
+        return;
+    }
+
+    references.push(reference);
+}
@@ -0,0 +1,330 @@
+use crate::{
+    add_implied_provider_requirement, get_depends_on, parse_provider_ref, take_overridden,
+    unexpected_labels, Error, Module, ProviderRef, Result,
+};
+use std::{collections::HashMap, path::Path};
+
+/// A `resource` block declared in the module.
+#[derive(Debug, Default, Clone)]
+pub struct ManagedResource {
+    pub type_: String,
+    pub name: String,
+    /// The provider configuration selected by the `provider` meta-argument, if set.
+    pub provider: Option<ProviderRef>,
+    /// Labels of the `dynamic` blocks found anywhere within the resource, in source order.
+    pub dynamic_blocks: Vec<String>,
+    /// Types of the resource's `provisioner` blocks, such as `local-exec`, in source order.
+    pub provisioners: Vec<String>,
+    /// Addresses of the objects listed in the `depends_on` meta-argument.
+    pub depends_on: Vec<String>,
+    /// The `count` meta-argument, if set.
+    pub count: Option<hcl::Expression>,
+    /// The `for_each` meta-argument, if set.
+    pub for_each: Option<hcl::Expression>,
+    /// Top-level attributes of the resource's body, keyed by name, excluding meta-arguments.
+    pub attributes: HashMap<String, hcl::Expression>,
+    /// Blocks nested in the resource's body, such as `ebs_block_device` or `lifecycle`, paired
+    /// with their block type, in source order.
+    pub nested_blocks: Vec<(String, hcl::Body)>,
+    /// The `ignore_changes` argument of the resource's `lifecycle` block, if set.
+    pub ignore_changes: Option<IgnoreChanges>,
+}
+
+/// The `ignore_changes` argument of a resource's `lifecycle` block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IgnoreChanges {
+    /// The `all` keyword, which ignores changes to every attribute once the resource is created.
+    All,
+    /// The attributes whose changes are ignored, such as `tags` or `tags["Name"]`, in source
+    /// order.
+    Attributes(Vec<String>),
+}
+
+impl ManagedResource {
+    pub fn new(type_: String, name: String) -> Self {
+        Self {
+            type_,
+            name,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the resource's address, such as `aws_instance.web`.
+    pub fn address(&self) -> String {
+        format!("{}.{}", self.type_, self.name)
+    }
+
+    /// Returns the local name of the provider implied by the resource's type.
+    pub fn implied_provider(&self) -> &str {
+        implied_provider(&self.type_)
+    }
+
+    /// Returns the local name of the provider the resource uses, which is the one selected by the
+    /// `provider` meta-argument or else the one implied by its type.
+    pub fn provider_name(&self) -> &str {
+        match &self.provider {
+            Some(provider) => &provider.name,
+            None => self.implied_provider(),
+        }
+    }
+}
+
+/// A `data` block declared in the module.
+#[derive(Debug, Default, Clone)]
+pub struct DataResource {
+    pub type_: String,
+    pub name: String,
+    /// The provider configuration selected by the `provider` meta-argument, if set.
+    pub provider: Option<ProviderRef>,
+    /// Addresses of the objects listed in the `depends_on` meta-argument.
+    pub depends_on: Vec<String>,
+    /// The `count` meta-argument, if set.
+    pub count: Option<hcl::Expression>,
+    /// The `for_each` meta-argument, if set.
+    pub for_each: Option<hcl::Expression>,
+    /// Top-level attributes of the data source's body, keyed by name, excluding meta-arguments.
+    pub attributes: HashMap<String, hcl::Expression>,
+    /// Blocks nested in the data source's body, paired with their block type, in source order.
+    pub nested_blocks: Vec<(String, hcl::Body)>,
+}
+
+impl DataResource {
+    pub fn new(type_: String, name: String) -> Self {
+        Self {
+            type_,
+            name,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the data source's address, such as `data.aws_ami.ubuntu`.
+    pub fn address(&self) -> String {
+        format!("data.{}.{}", self.type_, self.name)
+    }
+
+    /// Returns the local name of the provider implied by the data source's type.
+    pub fn implied_provider(&self) -> &str {
+        implied_provider(&self.type_)
+    }
+
+    /// Returns the local name of the provider the data source uses, which is the one selected by
+    /// the `provider` meta-argument or else the one implied by its type.
+    pub fn provider_name(&self) -> &str {
+        match &self.provider {
+            Some(provider) => &provider.name,
+            None => self.implied_provider(),
+        }
+    }
+}
+
+fn implied_provider(type_: &str) -> &str {
+    type_.split('_').next().unwrap_or(type_)
+}
+
+pub(crate) fn handle_resource_block(
+    current_file: &Path,
+    block: &hcl::Block,
+    module: &mut Module,
+) -> Result<()> {
+    let (type_, name) = get_type_and_name(current_file, block)?;
+
+    let address = format!("{type_}.{name}");
+    let mut resource = take_overridden(current_file, &mut module.managed_resources, &address)
+        .unwrap_or_else(|| ManagedResource::new(type_, name));
+    if let Some(provider) = get_provider(current_file, block.body())? {
+        resource.provider = Some(provider);
+    }
+    // Nested blocks in an override file replace all blocks of the same kind.
+    let mut dynamic_blocks = vec![];
+    collect_dynamic_blocks(block.body(), &mut dynamic_blocks);
+    if !dynamic_blocks.is_empty() {
+        resource.dynamic_blocks = dynamic_blocks;
+    }
+    let provisioners = get_provisioners(block.body());
+    if !provisioners.is_empty() {
+        resource.provisioners = provisioners;
+    }
+    if let Some(depends_on) = get_depends_on(current_file, block.body())? {
+        resource.depends_on = depends_on;
+    }
+    set_repetition(block.body(), &mut resource.count, &mut resource.for_each);
+    resource.attributes.extend(get_attributes(block.body()));
+    merge_nested_blocks(block.body(), &mut resource.nested_blocks);
+    if let Some(ignore_changes) = get_ignore_changes(current_file, block.body())? {
+        resource.ignore_changes = Some(ignore_changes);
+    }
+
+    add_implied_provider_requirement(resource.provider_name(), module);
+    module
+        .managed_resources
+        .insert(resource.address(), resource);
+
+    Ok(())
+}
+
+pub(crate) fn handle_data_block(
+    current_file: &Path,
+    block: &hcl::Block,
+    module: &mut Module,
+) -> Result<()> {
+    let (type_, name) = get_type_and_name(current_file, block)?;
+
+    let address = format!("data.{type_}.{name}");
+    let mut resource = take_overridden(current_file, &mut module.data_resources, &address)
+        .unwrap_or_else(|| DataResource::new(type_, name));
+    if let Some(provider) = get_provider(current_file, block.body())? {
+        resource.provider = Some(provider);
+    }
+    if let Some(depends_on) = get_depends_on(current_file, block.body())? {
+        resource.depends_on = depends_on;
+    }
+    set_repetition(block.body(), &mut resource.count, &mut resource.for_each);
+    resource.attributes.extend(get_attributes(block.body()));
+    merge_nested_blocks(block.body(), &mut resource.nested_blocks);
+
+    add_implied_provider_requirement(resource.provider_name(), module);
+    module.data_resources.insert(resource.address(), resource);
+
+    Ok(())
+}
+
+/// Records the provider requirement implied by a `resource` or `data` block, without
+/// interpreting the rest of the block.
+pub(crate) fn handle_resource_provider(
+    current_file: &Path,
+    block: &hcl::Block,
+    module: &mut Module,
+) -> Result<()> {
+    let (type_, _) = get_type_and_name(current_file, block)?;
+
+    match get_provider(current_file, block.body())? {
+        Some(provider) => add_implied_provider_requirement(&provider.name, module),
+        None => add_implied_provider_requirement(implied_provider(&type_), module),
+    }
+
+    Ok(())
+}
+
+fn get_type_and_name(current_file: &Path, block: &hcl::Block) -> Result<(String, String)> {
+    match block.labels() {
+        [type_, name] => Ok((type_.as_str().to_string(), name.as_str().to_string())),
+        _ => Err(unexpected_labels(current_file, block)),
+    }
+}
+
+/// Parses the `provider` meta-argument of a resource body, which selects a provider
+/// configuration by `name` or `name.alias`.
+fn get_provider(current_file: &Path, body: &hcl::Body) -> Result<Option<ProviderRef>> {
+    let Some(attr) = body.attributes().find(|attr| attr.key() == "provider") else {
+        return Ok(None);
+    };
+
+    match parse_provider_ref(attr.expr()) {
+        Some(provider) => Ok(Some(provider)),
+        None => Err(Error::UnexpectedExpr {
+            attribute_key: attr.key().to_string(),
+            expr: attr.expr().clone(),
+            file_name: current_file.to_path_buf(),
+        }),
+    }
+}
+
+/// Parses the `ignore_changes` argument of a resource's `lifecycle` block, which is either the
+/// `all` keyword or a list of attribute paths. Terraform 0.11 also accepted quoted paths.
+fn get_ignore_changes(current_file: &Path, body: &hcl::Body) -> Result<Option<IgnoreChanges>> {
+    let Some(attr) = body
+        .blocks()
+        .filter(|inner_block| inner_block.identifier() == "lifecycle")
+        .flat_map(|inner_block| inner_block.body().attributes())
+        .find(|attr| attr.key() == "ignore_changes")
+    else {
+        return Ok(None);
+    };
+
+    let unexpected_expr = || Error::UnexpectedExpr {
+        attribute_key: attr.key().to_string(),
+        expr: attr.expr().clone(),
+        file_name: current_file.to_path_buf(),
+    };
+
+    match attr.expr() {
+        hcl::Expression::Variable(keyword) if keyword.as_str() == "all" => {
+            Ok(Some(IgnoreChanges::All))
+        }
+        hcl::Expression::Array(paths) => paths
+            .iter()
+            .map(|path| match path {
+                hcl::Expression::String(path) => Ok(path.clone()),
+                hcl::Expression::Variable(_) | hcl::Expression::Traversal(_) => {
+                    Ok(path.to_string())
+                }
+                _ => Err(unexpected_expr()),
+            })
+            .collect::<Result<_>>()
+            .map(|paths| Some(IgnoreChanges::Attributes(paths))),
+        _ => Err(unexpected_expr()),
+    }
+}
+
+/// Meta-arguments that are handled by Terraform rather than passed to the provider.
+const META_ARGUMENTS: &[&str] = &["count", "depends_on", "for_each", "lifecycle", "provider"];
+
+fn get_attributes(body: &hcl::Body) -> HashMap<String, hcl::Expression> {
+    body.attributes()
+        .filter(|attr| !META_ARGUMENTS.contains(&attr.key()))
+        .map(|attr| (attr.key().to_string(), attr.expr().clone()))
+        .collect()
+}
+
+/// Sets the `count` and `for_each` meta-arguments from the given body, keeping any existing value
+/// for an argument the body does not set.
+fn set_repetition(
+    body: &hcl::Body,
+    count: &mut Option<hcl::Expression>,
+    for_each: &mut Option<hcl::Expression>,
+) {
+    for attr in body.attributes() {
+        match attr.key() {
+            "count" => *count = Some(attr.expr().clone()),
+            "for_each" => *for_each = Some(attr.expr().clone()),
+            _ => (),
+        }
+    }
+}
+
+/// Adds the blocks nested in the given body to `nested_blocks`. As in Terraform override files,
+/// they replace any existing blocks of the same type.
+pub(crate) fn merge_nested_blocks(body: &hcl::Body, nested_blocks: &mut Vec<(String, hcl::Body)>) {
+    let new_blocks: Vec<_> = body
+        .blocks()
+        .map(|inner_block| {
+            (
+                inner_block.identifier().to_string(),
+                inner_block.body().clone(),
+            )
+        })
+        .collect();
+
+    nested_blocks.retain(|(type_, _)| !new_blocks.iter().any(|(new_type, _)| new_type == type_));
+    nested_blocks.extend(new_blocks);
+}
+
+fn get_provisioners(body: &hcl::Body) -> Vec<String> {
+    body.blocks()
+        .filter(|inner_block| inner_block.identifier() == "provisioner")
+        .filter_map(|inner_block| inner_block.labels().first())
+        .map(|label| label.as_str().to_string())
+        .collect()
+}
+
+fn collect_dynamic_blocks(body: &hcl::Body, dynamic_blocks: &mut Vec<String>) {
+    for inner_block in body.blocks() {
+        if inner_block.identifier() == "dynamic" {
+            if let Some(label) = inner_block.labels().first() {
+                dynamic_blocks.push(label.as_str().to_string());
+            }
+        }
+
+        collect_dynamic_blocks(inner_block.body(), dynamic_blocks);
+    }
+}
@@ -0,0 +1,44 @@
+use crate::{load_body, load_dir, parse_file_contents, LoadOptions, Module, Result};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+/// A [`Module`][Module] along with the formatting-preserving syntax tree of each of its files.
+#[derive(Debug, Default, Clone)]
+pub struct RichModule {
+    pub module: Module,
+    /// Documents of the files that were parsed, keyed by file path. Unlike the module itself,
+    /// these keep comments, whitespace and the original formatting of each file.
+    pub documents: HashMap<PathBuf, hcl::edit::structure::Body>,
+}
+
+/// Reads the directory at the given path and attempts to interpret it as a Terraform module, also
+/// keeping the [`hcl::edit`][hcl::edit] document of each file.
+///
+/// This is slower and uses more memory than [`load_module`][crate::load_module], but allows
+/// inspecting comments and making formatting-preserving edits.
+///
+/// # Arguments
+///
+/// * `path` - Path to the directory containing the Terraform configuration
+/// * `strict` - Whether to immediately return an error if a file in the directory cannot be parsed.
+///   Otherwise, the file is skipped and an error diagnostic is recorded on the module.
+pub fn load_module_rich(path: &Path, strict: bool) -> Result<RichModule> {
+    let options = LoadOptions::new().strict(strict);
+    let mut documents = HashMap::new();
+
+    let module = load_dir(path, &options, |file_name, file_contents, module| {
+        let Some(document) = parse_file_contents(file_name, file_contents, &options, module)?
+        else {
+            return Ok(());
+        };
+
+        load_body(file_name, document.clone().into(), &options, module)?;
+        documents.insert(file_name.to_path_buf(), document);
+
+        Ok(())
+    })?;
+
+    Ok(RichModule { module, documents })
+}
@@ -0,0 +1,133 @@
+use crate::{Module, VarType, Variable};
+use hcl::{Expression, ObjectKey};
+use serde_json::{json, Map, Value};
+
+impl Module {
+    /// Returns a JSON Schema describing valid values for the module's input variables, such as in
+    /// a `terraform.tfvars.json` file.
+    ///
+    /// Each variable becomes a property described by its type constraint, `description` and
+    /// `default`, and variables without a default are required. Within `object(...)` types,
+    /// attributes wrapped in `optional(...)` are not required.
+    pub fn variables_json_schema(&self) -> Value {
+        let mut names: Vec<&String> = self.variables.keys().collect();
+        names.sort();
+
+        let mut properties = Map::new();
+        let mut required = vec![];
+        for name in names {
+            let variable = &self.variables[name];
+            properties.insert(name.clone(), variable_schema(variable));
+            if variable.is_required() {
+                required.push(Value::String(name.clone()));
+            }
+        }
+
+        json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "type": "object",
+            "properties": properties,
+            "required": required,
+        })
+    }
+}
+
+fn variable_schema(variable: &Variable) -> Value {
+    let mut schema = match variable.var_type() {
+        Some(type_) => type_schema(&type_),
+        None => Map::new(),
+    };
+
+    if !variable.description.is_empty() {
+        schema.insert(
+            "description".to_string(),
+            Value::String(variable.description.clone()),
+        );
+    }
+    if let Some(default) = variable.default.as_ref().and_then(literal_value) {
+        schema.insert("default".to_string(), default);
+    }
+
+    Value::Object(schema)
+}
+
+/// Converts a type constraint into a schema. Types that cannot be described, such as `any`,
+/// allow any value.
+fn type_schema(type_: &VarType) -> Map<String, Value> {
+    let schema = match type_ {
+        VarType::Any => json!({}),
+        VarType::String => json!({ "type": "string" }),
+        VarType::Number => json!({ "type": "number" }),
+        VarType::Bool => json!({ "type": "boolean" }),
+        VarType::List(element) => json!({ "type": "array", "items": type_schema(element) }),
+        VarType::Set(element) => json!({
+            "type": "array",
+            "items": type_schema(element),
+            "uniqueItems": true,
+        }),
+        VarType::Map(element) => json!({
+            "type": "object",
+            "additionalProperties": type_schema(element),
+        }),
+        VarType::Tuple(elements) => json!({
+            "type": "array",
+            "prefixItems": elements.iter().map(type_schema).collect::<Vec<_>>(),
+            "items": false,
+        }),
+        VarType::Object(attributes) => {
+            let mut properties = Map::new();
+            let mut required = vec![];
+            for (name, attribute) in attributes {
+                if !matches!(attribute, VarType::Optional(_)) {
+                    required.push(Value::String(name.clone()));
+                }
+                properties.insert(name.clone(), Value::Object(type_schema(attribute)));
+            }
+
+            json!({
+                "type": "object",
+                "properties": properties,
+                "required": required,
+            })
+        }
+        VarType::Optional(type_) => return type_schema(type_),
+    };
+
+    match schema {
+        Value::Object(schema) => schema,
+        _ => Map::new(),
+    }
+}
+
+fn object_key_name(key: &ObjectKey) -> String {
+    match key {
+        ObjectKey::Identifier(name) => name.to_string(),
+        ObjectKey::Expression(Expression::String(name)) => name.clone(),
+        key => key.to_string().replace('"', ""),
+    }
+}
+
+/// Converts a literal expression into a JSON value, returning `None` if it is not a literal.
+fn literal_value(expr: &Expression) -> Option<Value> {
+    match expr {
+        Expression::Null => Some(Value::Null),
+        Expression::Bool(value) => Some(Value::Bool(*value)),
+        Expression::Number(number) => {
+            let number = match (number.as_i64(), number.as_u64(), number.as_f64()) {
+                (Some(value), _, _) => value.into(),
+                (_, Some(value), _) => value.into(),
+                (_, _, Some(value)) => serde_json::Number::from_f64(value)?,
+                _ => return None,
+            };
+            Some(Value::Number(number))
+        }
+        Expression::String(value) => Some(Value::String(value.clone())),
+        Expression::Array(elements) => elements.iter().map(literal_value).collect(),
+        Expression::Object(attributes) => attributes
+            .iter()
+            .map(|(key, value)| Some((object_key_name(key), literal_value(value)?)))
+            .collect::<Option<Map<_, _>>>()
+            .map(Value::Object),
+        _ => None,
+    }
+}
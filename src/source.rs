@@ -0,0 +1,86 @@
+use std::fmt;
+
+/// Hostname used in the source addresses of providers built into Terraform.
+pub const BUILTIN_PROVIDER_HOST: &str = "terraform.io";
+
+/// Hostname of the registry that provider sources without an explicit hostname refer to.
+pub const DEFAULT_PROVIDER_HOST: &str = "registry.terraform.io";
+
+/// A provider source address of the form `[hostname/]namespace/type`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SourceAddr {
+    pub hostname: String,
+    pub namespace: String,
+    pub type_: String,
+}
+
+impl SourceAddr {
+    pub fn new(hostname: String, namespace: String, type_: String) -> Self {
+        Self {
+            hostname,
+            namespace,
+            type_,
+        }
+    }
+
+    /// Parses a provider source address, filling in [`DEFAULT_PROVIDER_HOST`][DEFAULT_PROVIDER_HOST]
+    /// if no hostname is given.
+    ///
+    /// Returns `None` if the source does not have two or three non-empty parts.
+    pub fn parse(source: &str) -> Option<Self> {
+        let parts: Vec<&str> = source.split('/').collect();
+        if parts.iter().any(|part| part.is_empty()) {
+            return None;
+        }
+
+        match parts.as_slice() {
+            [namespace, type_] => Some(Self::new(
+                DEFAULT_PROVIDER_HOST.to_string(),
+                namespace.to_string(),
+                type_.to_string(),
+            )),
+            [hostname, namespace, type_] => Some(Self::new(
+                hostname.to_string(),
+                namespace.to_string(),
+                type_.to_string(),
+            )),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the hostname is a syntactically valid DNS name, optionally followed by a
+    /// `:port`.
+    ///
+    /// Each dot-separated label must be 1 to 63 ASCII letters, digits or hyphens, and must not start
+    /// or end with a hyphen. Internationalized hostnames must be given in their ASCII form.
+    pub fn is_valid_host(&self) -> bool {
+        let (host, port) = match self.hostname.rsplit_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (self.hostname.as_str(), None),
+        };
+
+        if let Some(port) = port {
+            if port.is_empty() || !port.bytes().all(|b| b.is_ascii_digit()) || port.len() > 5 {
+                return false;
+            }
+        }
+
+        !host.is_empty()
+            && host.len() <= 253
+            && host.split('.').all(|label| {
+                !label.is_empty()
+                    && label.len() <= 63
+                    && !label.starts_with('-')
+                    && !label.ends_with('-')
+                    && label
+                        .bytes()
+                        .all(|b| b.is_ascii_alphanumeric() || b == b'-')
+            })
+    }
+}
+
+impl fmt::Display for SourceAddr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}/{}", self.hostname, self.namespace, self.type_)
+    }
+}
@@ -0,0 +1,58 @@
+use crate::Module;
+
+/// A `moved` block recording that an object's address has changed.
+#[derive(Debug, Default, Clone)]
+pub struct MovedBlock {
+    pub from: String,
+    pub to: String,
+}
+
+/// An `import` block bringing an existing object under management.
+#[derive(Debug, Default, Clone)]
+pub struct ImportBlock {
+    pub to: String,
+    pub id: Option<hcl::Expression>,
+}
+
+/// A `removed` block removing an object from state without destroying it.
+#[derive(Debug, Default, Clone)]
+pub struct RemovedBlock {
+    pub from: String,
+}
+
+pub(crate) fn handle_moved_block(block: &hcl::Block, module: &mut Module) {
+    let mut moved = MovedBlock::default();
+    for attr in block.body().attributes() {
+        match attr.key() {
+            "from" => moved.from = attr.expr().to_string(),
+            "to" => moved.to = attr.expr().to_string(),
+            _ => (),
+        }
+    }
+
+    module.moved_blocks.push(moved);
+}
+
+pub(crate) fn handle_import_block(block: &hcl::Block, module: &mut Module) {
+    let mut import = ImportBlock::default();
+    for attr in block.body().attributes() {
+        match attr.key() {
+            "to" => import.to = attr.expr().to_string(),
+            "id" => import.id = Some(attr.expr().clone()),
+            _ => (),
+        }
+    }
+
+    module.import_blocks.push(import);
+}
+
+pub(crate) fn handle_removed_block(block: &hcl::Block, module: &mut Module) {
+    let mut removed = RemovedBlock::default();
+    for attr in block.body().attributes() {
+        if attr.key() == "from" {
+            removed.from = attr.expr().to_string();
+        }
+    }
+
+    module.removed_blocks.push(removed);
+}
@@ -0,0 +1,70 @@
+use crate::Module;
+use std::{collections::BTreeMap, path::PathBuf};
+
+/// A compact, owned projection of a [`Module`][Module] containing only plain data, suitable for
+/// caching or serializing.
+///
+/// Collections are sorted so that summaries of the same module are always identical.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ModuleSummary {
+    pub path: PathBuf,
+    pub required_core: Vec<String>,
+    /// Provider requirements, keyed by local name.
+    pub providers: BTreeMap<String, ProviderSummary>,
+    /// Names of the module's input variables.
+    pub variables: Vec<String>,
+    /// Number of managed resources of each resource type.
+    pub resource_types: BTreeMap<String, usize>,
+    /// Number of data resources of each data source type.
+    pub data_resource_types: BTreeMap<String, usize>,
+}
+
+/// The source and version constraints of a provider requirement within a
+/// [`ModuleSummary`][ModuleSummary].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProviderSummary {
+    pub source: String,
+    pub version_constraints: Vec<String>,
+}
+
+impl From<&Module> for ModuleSummary {
+    fn from(module: &Module) -> Self {
+        let providers = module
+            .required_providers
+            .iter()
+            .map(|(name, req)| {
+                let summary = ProviderSummary {
+                    source: req.source.clone(),
+                    version_constraints: req.version_constraints.clone(),
+                };
+                (name.clone(), summary)
+            })
+            .collect();
+
+        let mut variables: Vec<String> = module.variables.keys().cloned().collect();
+        variables.sort();
+
+        let mut resource_types = BTreeMap::new();
+        for resource in module.managed_resources.values() {
+            *resource_types.entry(resource.type_.clone()).or_default() += 1;
+        }
+
+        let mut data_resource_types = BTreeMap::new();
+        for resource in module.data_resources.values() {
+            *data_resource_types
+                .entry(resource.type_.clone())
+                .or_default() += 1;
+        }
+
+        Self {
+            path: module.path.clone(),
+            required_core: module.required_core.clone(),
+            providers,
+            variables,
+            resource_types,
+            data_resource_types,
+        }
+    }
+}
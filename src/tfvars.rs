@@ -0,0 +1,109 @@
+use crate::{
+    handle_read_error, parse_file_contents, unquote, Diagnostic, DiagnosticSeverity, Error,
+    LoadOptions, Module, Result, SourcePos,
+};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Reads the variable definitions files Terraform loads automatically from the module's directory
+/// into [`Module::variable_values`][Module::variable_values].
+///
+/// As in Terraform, `terraform.tfvars` and `terraform.tfvars.json` are read first, followed by
+/// any `*.auto.tfvars` and `*.auto.tfvars.json` files in lexical order of their names. A value
+/// set in a later file replaces one set in an earlier file.
+pub(crate) fn load_variable_values(
+    path: &Path,
+    options: &LoadOptions,
+    module: &mut Module,
+) -> Result<()> {
+    for file_name in get_variable_files(path)? {
+        let contents = match fs::read_to_string(&file_name) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => {
+                handle_read_error(&file_name, e, options, module)?;
+                continue;
+            }
+        };
+
+        if file_name.to_string_lossy().ends_with(".json") {
+            load_json_file(&file_name, &contents, options, module)?;
+        } else if let Some(body) = parse_file_contents(&file_name, &contents, options, module)? {
+            for attr in hcl::Body::from(body).attributes() {
+                module
+                    .variable_values
+                    .insert(attr.key().to_string(), attr.expr().clone());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn get_variable_files(path: &Path) -> Result<Vec<PathBuf>> {
+    let mut auto_files = vec![];
+    for entry in fs::read_dir(path)? {
+        let file = entry?.path();
+        let is_auto_file = file
+            .file_name()
+            .and_then(|file_name| file_name.to_str())
+            .is_some_and(|file_name| {
+                file_name.ends_with(".auto.tfvars") || file_name.ends_with(".auto.tfvars.json")
+            });
+        if is_auto_file && !file.is_dir() {
+            auto_files.push(file);
+        }
+    }
+    auto_files.sort();
+
+    let mut files = vec![
+        path.join("terraform.tfvars"),
+        path.join("terraform.tfvars.json"),
+    ];
+    files.append(&mut auto_files);
+    Ok(files)
+}
+
+/// Reads a `.tfvars.json` file, which holds a single JSON object. JSON is a subset of the HCL
+/// expression syntax, so the object is parsed as an HCL expression.
+fn load_json_file(
+    file_name: &Path,
+    contents: &str,
+    options: &LoadOptions,
+    module: &mut Module,
+) -> Result<()> {
+    let object = match hcl::edit::parser::parse_expr(contents).map(hcl::Expression::from) {
+        Ok(hcl::Expression::Object(object)) => object,
+        Ok(_) => {
+            module.diagnostics.push(Diagnostic::new(
+                DiagnosticSeverity::Error,
+                "Invalid variable values file".to_string(),
+                format!("{} does not contain a JSON object.", file_name.display()),
+            ));
+            return Ok(());
+        }
+        Err(e) if options.strict => return Err(Error::Parse(hcl::Error::Parse(e))),
+        Err(e) => {
+            let pos = SourcePos::new(file_name.to_path_buf(), e.location().line());
+            module.diagnostics.push(
+                Diagnostic::new(
+                    DiagnosticSeverity::Error,
+                    "Failed to parse file".to_string(),
+                    e.to_string(),
+                )
+                .with_pos(pos),
+            );
+            return Ok(());
+        }
+    };
+
+    for (key, value) in object {
+        module
+            .variable_values
+            .insert(unquote(&key.to_string()).to_string(), value);
+    }
+
+    Ok(())
+}
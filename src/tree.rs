@@ -0,0 +1,95 @@
+use crate::{
+    load_module_with_options, Diagnostic, DiagnosticSeverity, LoadOptions, Module, Result,
+};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+/// A module along with the local modules it calls, loaded recursively.
+#[derive(Debug, Default, Clone)]
+pub struct ModuleTree {
+    pub module: Module,
+    /// Trees of the modules called with a local source, keyed by module call name.
+    pub children: HashMap<String, ModuleTree>,
+}
+
+/// Loads the module at the given path and, recursively, every module it calls with a local
+/// source.
+///
+/// Modules from registries or remote sources are not fetched. Recursion stops at the depth set by
+/// [`LoadOptions::max_depth`][LoadOptions::max_depth], and at calls back into a module that is
+/// already being loaded; in both cases a warning is recorded on the calling module.
+///
+/// # Arguments
+///
+/// * `path` - Path to the directory of the root module
+/// * `options` - Options controlling how each module is loaded
+pub fn load_module_tree(path: &Path, options: &LoadOptions) -> Result<ModuleTree> {
+    let mut ancestors = vec![];
+    load_module_subtree(path, options, 0, &mut ancestors)
+}
+
+fn load_module_subtree(
+    path: &Path,
+    options: &LoadOptions,
+    depth: usize,
+    ancestors: &mut Vec<PathBuf>,
+) -> Result<ModuleTree> {
+    let mut module = load_module_with_options(path, options)?;
+
+    let mut calls: Vec<(String, PathBuf)> = module
+        .module_calls
+        .values()
+        .filter_map(|call| {
+            let child_path = call.resolved_local_path(path)?;
+            Some((call.name.clone(), child_path))
+        })
+        .collect();
+    calls.sort();
+
+    if options
+        .max_depth
+        .is_some_and(|max_depth| depth >= max_depth)
+    {
+        for (name, _) in calls {
+            module.diagnostics.push(Diagnostic::new(
+                DiagnosticSeverity::Warning,
+                "Module tree depth limit reached".to_string(),
+                format!(
+                    "The module called \"{name}\" from {} was not loaded because the maximum depth of {depth} was reached.",
+                    path.display()
+                ),
+            ));
+        }
+
+        return Ok(ModuleTree {
+            module,
+            children: HashMap::new(),
+        });
+    }
+
+    ancestors.push(path.canonicalize()?);
+
+    let mut children = HashMap::new();
+    for (name, child_path) in calls {
+        if ancestors.contains(&child_path) {
+            module.diagnostics.push(Diagnostic::new(
+                DiagnosticSeverity::Warning,
+                "Module call cycle".to_string(),
+                format!(
+                    "The module called \"{name}\" from {} was not loaded because it is already being loaded.",
+                    path.display()
+                ),
+            ));
+            continue;
+        }
+
+        let child = load_module_subtree(&child_path, options, depth + 1, ancestors)?;
+        children.insert(name, child);
+    }
+
+    ancestors.pop();
+
+    Ok(ModuleTree { module, children })
+}
@@ -0,0 +1,175 @@
+use crate::{get_block_name, take_overridden, Diagnostic, DiagnosticSeverity, Module, Result};
+use std::{collections::BTreeMap, path::Path};
+
+/// A `variable` block declared in the module.
+#[derive(Debug, Default, Clone)]
+pub struct Variable {
+    pub name: String,
+    pub description: String,
+    /// The type constraint, such as `string` or `list(object({ name = string }))`.
+    pub type_: Option<hcl::Expression>,
+    pub default: Option<hcl::Expression>,
+    /// The `sensitive` argument, or `None` if it is not set or not a literal bool. The same
+    /// applies to `nullable` and `ephemeral`.
+    pub sensitive: Option<bool>,
+    pub nullable: Option<bool>,
+    pub ephemeral: Option<bool>,
+}
+
+impl Variable {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            ..Default::default()
+        }
+    }
+
+    /// Returns whether a value must be provided for the variable, which is the case when it has no
+    /// `default`. A `default` of `null` still makes the variable optional.
+    pub fn is_required(&self) -> bool {
+        self.default.is_none()
+    }
+
+    /// Returns the structure of the variable's type constraint, or `None` if it has none or it is
+    /// not a valid type constraint.
+    pub fn var_type(&self) -> Option<VarType> {
+        self.type_.as_ref().and_then(VarType::parse)
+    }
+}
+
+/// The structure of a variable's type constraint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VarType {
+    /// The `any` placeholder, which accepts a value of any type.
+    Any,
+    String,
+    Number,
+    Bool,
+    List(Box<VarType>),
+    Set(Box<VarType>),
+    Map(Box<VarType>),
+    Tuple(Vec<VarType>),
+    /// An `object(...)` type, with its attribute types keyed by name.
+    Object(BTreeMap<String, VarType>),
+    /// An object attribute wrapped in `optional(...)`, which may be omitted. Its default value, if
+    /// any, is not kept.
+    Optional(Box<VarType>),
+}
+
+impl VarType {
+    /// Parses a type constraint expression, such as `list(string)`, returning `None` if it is not
+    /// a valid type constraint.
+    ///
+    /// The quoted type names of Terraform 0.11, `"string"`, `"list"` and `"map"`, are also
+    /// accepted.
+    pub fn parse(expr: &hcl::Expression) -> Option<Self> {
+        match expr {
+            hcl::Expression::Variable(name) => match name.as_str() {
+                "any" => Some(Self::Any),
+                "string" => Some(Self::String),
+                "number" => Some(Self::Number),
+                "bool" => Some(Self::Bool),
+                _ => None,
+            },
+            hcl::Expression::String(name) => match name.as_str() {
+                "string" => Some(Self::String),
+                "list" => Some(Self::List(Box::new(Self::Any))),
+                "map" => Some(Self::Map(Box::new(Self::Any))),
+                _ => None,
+            },
+            hcl::Expression::FuncCall(func) => {
+                if !func.name.namespace.is_empty() {
+                    return None;
+                }
+
+                match (func.name.name.as_str(), func.args.as_slice()) {
+                    ("list", [element]) => Some(Self::List(Box::new(Self::parse(element)?))),
+                    ("set", [element]) => Some(Self::Set(Box::new(Self::parse(element)?))),
+                    ("map", [element]) => Some(Self::Map(Box::new(Self::parse(element)?))),
+                    ("tuple", [hcl::Expression::Array(elements)]) => elements
+                        .iter()
+                        .map(Self::parse)
+                        .collect::<Option<_>>()
+                        .map(Self::Tuple),
+                    ("object", [hcl::Expression::Object(attributes)]) => attributes
+                        .iter()
+                        .map(|(key, attribute)| {
+                            let type_ = match attribute {
+                                hcl::Expression::FuncCall(func)
+                                    if func.name.name.as_str() == "optional"
+                                        && (1..=2).contains(&func.args.len()) =>
+                                {
+                                    Self::Optional(Box::new(Self::parse(&func.args[0])?))
+                                }
+                                attribute => Self::parse(attribute)?,
+                            };
+                            Some((key.to_string().replace('"', ""), type_))
+                        })
+                        .collect::<Option<_>>()
+                        .map(Self::Object),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+pub(crate) fn handle_variable_block(
+    current_file: &Path,
+    block: &hcl::Block,
+    module: &mut Module,
+) -> Result<()> {
+    let name = get_block_name(current_file, block)?;
+
+    let mut variable = take_overridden(current_file, &mut module.variables, &name)
+        .unwrap_or_else(|| Variable::new(name));
+    for attr in block.body().attributes() {
+        match (attr.key(), attr.expr()) {
+            ("description", hcl::Expression::String(description)) => {
+                variable.description = description.clone()
+            }
+            ("type", type_) => variable.type_ = Some(type_.clone()),
+            ("default", default) => variable.default = Some(default.clone()),
+            ("sensitive", expr) => {
+                variable.sensitive = get_bool(current_file, &variable, attr.key(), expr, module)
+            }
+            ("nullable", expr) => {
+                variable.nullable = get_bool(current_file, &variable, attr.key(), expr, module)
+            }
+            ("ephemeral", expr) => {
+                variable.ephemeral = get_bool(current_file, &variable, attr.key(), expr, module)
+            }
+            _ => (),
+        }
+    }
+
+    module.variables.insert(variable.name.clone(), variable);
+
+    Ok(())
+}
+
+/// Returns the value of a bool argument of a variable, recording a warning if it is not a literal
+/// bool, as its value cannot be known without evaluating the configuration.
+fn get_bool(
+    current_file: &Path,
+    variable: &Variable,
+    key: &str,
+    expr: &hcl::Expression,
+    module: &mut Module,
+) -> Option<bool> {
+    if let hcl::Expression::Bool(value) = expr {
+        return Some(*value);
+    }
+
+    module.diagnostics.push(Diagnostic::new(
+        DiagnosticSeverity::Warning,
+        "Non-literal variable argument".to_string(),
+        format!(
+            "The {key:?} argument of variable {:?} in {} is not a literal bool and was ignored.",
+            variable.name,
+            current_file.display()
+        ),
+    ));
+    None
+}
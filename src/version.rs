@@ -0,0 +1,264 @@
+use crate::{Error, Result};
+use std::{cmp::Ordering, str::FromStr};
+
+/// A version number as used in Terraform version constraints, such as `1.2.3` or `1.6.0-beta1`.
+///
+/// Versions are equal when they compare equal, regardless of how many segments were written out,
+/// so `1.2` equals `1.2.0`.
+#[derive(Debug, Clone)]
+pub(crate) struct Version {
+    segments: [u64; 3],
+    /// Number of segments that were written out, as `~> 1.2` differs from `~> 1.2.0`.
+    specified: usize,
+    prerelease: Option<String>,
+}
+
+impl Version {
+    fn core_eq(&self, other: &Self) -> bool {
+        self.segments == other.segments
+    }
+
+    /// Parses a version, returning `None` if it is malformed.
+    fn parse(s: &str) -> Option<Self> {
+        let s = s.strip_prefix('v').unwrap_or(s);
+        let s = s.split_once('+').map_or(s, |(version, _metadata)| version);
+        let (core, prerelease) = match s.split_once('-') {
+            Some((_, "")) => return None,
+            Some((core, prerelease)) => (core, Some(prerelease.to_string())),
+            None => (s, None),
+        };
+
+        let mut segments = [0; 3];
+        let mut specified = 0;
+        for part in core.split('.') {
+            if specified == segments.len() || part.is_empty() {
+                return None;
+            }
+            if !part.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            segments[specified] = part.parse().ok()?;
+            specified += 1;
+        }
+
+        Some(Self {
+            segments,
+            specified,
+            prerelease,
+        })
+    }
+}
+
+impl FromStr for Version {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Version::parse(s.trim()).ok_or_else(|| Error::InvalidVersion(s.to_string()))
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.segments.cmp(&other.segments).then_with(|| {
+            match (&self.prerelease, &other.prerelease) {
+                (None, None) => Ordering::Equal,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(_), None) => Ordering::Less,
+                (Some(a), Some(b)) => compare_prerelease(a, b),
+            }
+        })
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Version {}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Compares pre-release identifiers as in semver: numeric identifiers compare numerically and
+/// sort before alphanumeric ones, and a shorter list of identifiers sorts first.
+fn compare_prerelease(a: &str, b: &str) -> Ordering {
+    let mut a = a.split('.');
+    let mut b = b.split('.');
+    loop {
+        let ordering = match (a.next(), b.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(a), Some(b)) => match (a.parse::<u64>(), b.parse::<u64>()) {
+                (Ok(a), Ok(b)) => a.cmp(&b),
+                (Ok(_), Err(_)) => Ordering::Less,
+                (Err(_), Ok(_)) => Ordering::Greater,
+                (Err(_), Err(_)) => a.cmp(b),
+            },
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Operator {
+    Equal,
+    NotEqual,
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+    /// The `~>` operator, which only allows the rightmost specified segment to increase.
+    Pessimistic,
+}
+
+/// A single version constraint, such as `>= 1.2` or `~> 4.0`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Constraint {
+    pub(crate) operator: Operator,
+    pub(crate) version: Version,
+}
+
+impl Constraint {
+    /// Returns whether the given version satisfies the constraint.
+    ///
+    /// As in Terraform, a pre-release version only satisfies constraints that name a pre-release
+    /// of the same version, so `>= 1.0` does not allow `2.0.0-beta1`.
+    pub(crate) fn matches(&self, version: &Version) -> bool {
+        if version.prerelease.is_some()
+            && !(self.version.prerelease.is_some() && self.version.core_eq(version))
+        {
+            return false;
+        }
+
+        match self.operator {
+            Operator::Equal => version == &self.version,
+            Operator::NotEqual => version != &self.version,
+            Operator::Greater => version > &self.version,
+            Operator::GreaterOrEqual => version >= &self.version,
+            Operator::Less => version < &self.version,
+            Operator::LessOrEqual => version <= &self.version,
+            Operator::Pessimistic => {
+                let fixed = self.version.specified.saturating_sub(1);
+                version >= &self.version
+                    && version.segments[..fixed] == self.version.segments[..fixed]
+            }
+        }
+    }
+}
+
+impl FromStr for Constraint {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || Error::InvalidVersionConstraint(s.to_string());
+
+        let trimmed = s.trim();
+        let (operator, version) = [
+            ("~>", Operator::Pessimistic),
+            (">=", Operator::GreaterOrEqual),
+            ("<=", Operator::LessOrEqual),
+            ("!=", Operator::NotEqual),
+            (">", Operator::Greater),
+            ("<", Operator::Less),
+            ("=", Operator::Equal),
+        ]
+        .into_iter()
+        .find_map(|(prefix, operator)| Some((operator, trimmed.strip_prefix(prefix)?)))
+        .unwrap_or((Operator::Equal, trimmed));
+
+        let version = Version::parse(version.trim()).ok_or_else(invalid)?;
+
+        Ok(Self { operator, version })
+    }
+}
+
+/// Parses a comma-separated list of version constraints, such as `>= 1.0, < 2.0`.
+///
+/// Returns an [`Error::EmptyVersionConstraint`][Error::EmptyVersionConstraint] if any constraint
+/// in the list is empty, as with a trailing comma.
+fn parse_constraints(constraints: &str) -> Result<Vec<Constraint>> {
+    constraints
+        .split(',')
+        .map(|constraint| {
+            if constraint.trim().is_empty() {
+                Err(Error::EmptyVersionConstraint(constraints.to_string()))
+            } else {
+                constraint.parse()
+            }
+        })
+        .collect()
+}
+
+/// Returns whether the given version satisfies every one of the given constraints, each of which
+/// may itself be a comma-separated list.
+pub(crate) fn matches_all(constraints: &[String], version: &str) -> Result<bool> {
+    let version: Version = version.parse()?;
+
+    let mut matches = true;
+    for constraints in constraints {
+        for constraint in parse_constraints(constraints)? {
+            matches &= constraint.matches(&version);
+        }
+    }
+
+    Ok(matches)
+}
+
+/// Returns whether the given string is a non-empty, comma-separated list of valid version
+/// constraints.
+pub(crate) fn is_valid_constraints(constraints: &str) -> bool {
+    parse_constraints(constraints).is_ok()
+}
+
+/// How strictly a set of version constraints pins a provider, as returned by
+/// [`ProviderRequirement::pinning`][crate::ProviderRequirement::pinning].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pinning {
+    /// A single version is allowed, such as with `= 1.2.3`.
+    Exact,
+    /// Versions are bounded from above, such as with `~> 1.2` or `>= 1.0, < 2.0`.
+    Range,
+    /// Versions are only bounded from below, such as with `>= 1.0`.
+    Loose,
+    /// Any version is allowed.
+    Unpinned,
+}
+
+/// Classifies how strictly the given constraints pin a version. Constraints that cannot be parsed
+/// are ignored.
+pub(crate) fn pinning(constraints: &[String]) -> Pinning {
+    let mut lower_bound = false;
+    let mut upper_bound = false;
+
+    let constraints = constraints
+        .iter()
+        .flat_map(|constraints| constraints.split(','))
+        .filter_map(|constraint| constraint.parse::<Constraint>().ok());
+    for constraint in constraints {
+        match constraint.operator {
+            Operator::Equal => return Pinning::Exact,
+            Operator::NotEqual => (),
+            Operator::Greater | Operator::GreaterOrEqual => lower_bound = true,
+            Operator::Less | Operator::LessOrEqual => upper_bound = true,
+            // A single segment like `~> 1` only fixes a lower bound.
+            Operator::Pessimistic if constraint.version.specified < 2 => lower_bound = true,
+            Operator::Pessimistic => upper_bound = true,
+        }
+    }
+
+    if upper_bound {
+        Pinning::Range
+    } else if lower_bound {
+        Pinning::Loose
+    } else {
+        Pinning::Unpinned
+    }
+}
@@ -1,11 +1,12 @@
 use std::{
+    collections::{HashMap, HashSet},
     error::Error,
     fs::{self},
-    path::PathBuf,
+    path::{Path, PathBuf},
     result,
 };
 use tempdir::TempDir;
-use tfconfig::{Error as TfConfigError, Module};
+use tfconfig::{Diagnostic, DiagnosticSeverity, Error as TfConfigError, Module};
 
 #[test]
 fn test_load_module() -> result::Result<(), Box<dyn Error>> {
@@ -177,3 +178,4040 @@ fn test_load_module_read_to_string_fail_strict() -> Result<(), Box<dyn Error>> {
 
     Ok(())
 }
+
+#[test]
+fn test_module_providers() -> result::Result<(), Box<dyn Error>> {
+    let file: hcl::Body = hcl::from_str(
+        r#"terraform {
+            required_providers {
+                mycloud = {
+                    source  = "mycorp/mycloud"
+                    version = "~> 1.0"
+                }
+                othercloud = {
+                    source  = "othercorp/othercloud"
+                }
+            }
+        }"#,
+    )?;
+
+    let pathbuf = PathBuf::from("");
+    let mut module = Module::new(pathbuf.clone());
+    tfconfig::load_module_from_file(&pathbuf, file, &mut module)?;
+
+    assert_eq!(2, module.providers().len());
+
+    let mut names: Vec<&String> = module.providers().map(|(name, _)| name).collect();
+    names.sort();
+    assert_eq!(vec!["mycloud", "othercloud"], names);
+
+    let mut sources = vec![];
+    for (_, req) in &module {
+        sources.push(req.source.as_str());
+    }
+    sources.sort();
+    assert_eq!(vec!["mycorp/mycloud", "othercorp/othercloud"], sources);
+
+    Ok(())
+}
+
+#[test]
+fn test_load_module_from_file_dynamic_blocks() -> result::Result<(), Box<dyn Error>> {
+    let file: hcl::Body = hcl::from_str(
+        r#"resource "aws_security_group" "allow_tls" {
+            name = "allow_tls"
+
+            dynamic "ingress" {
+                for_each = var.ingress_rules
+                content {
+                    from_port = ingress.value.port
+                }
+            }
+
+            dynamic "egress" {
+                for_each = var.egress_rules
+                content {
+                    to_port = egress.value.port
+                }
+            }
+        }"#,
+    )?;
+
+    let pathbuf = PathBuf::from("");
+    let mut module = Module::new(pathbuf.clone());
+    tfconfig::load_module_from_file(&pathbuf, file, &mut module)?;
+
+    assert_eq!(1, module.managed_resources.len());
+    let resource = module.managed_resources.get("aws_security_group.allow_tls");
+    assert!(resource.is_some());
+    let resource = resource.unwrap();
+    assert_eq!("aws_security_group", resource.type_);
+    assert_eq!("allow_tls", resource.name);
+    assert_eq!(vec!["ingress", "egress"], resource.dynamic_blocks);
+
+    Ok(())
+}
+
+#[cfg(feature = "archive")]
+#[test]
+fn test_load_module_from_archive_zip() -> result::Result<(), Box<dyn Error>> {
+    use std::io::{Cursor, Write};
+    use zip::write::SimpleFileOptions;
+
+    let mut writer = zip::ZipWriter::new(Cursor::new(vec![]));
+    writer.start_file("versions.tf", SimpleFileOptions::default())?;
+    writer.write_all(
+        br#"terraform {
+            required_version = "1.0.0"
+
+            required_providers {
+                mycloud = {
+                    source  = "mycorp/mycloud"
+                    version = "~> 1.0"
+                }
+            }
+        }"#,
+    )?;
+    writer.start_file("main_override.tf", SimpleFileOptions::default())?;
+    writer.write_all(
+        br#"terraform {
+            required_providers {
+                mycloud = {
+                    source  = "mycorp/mycloud"
+                    version = "~> 2.0"
+                }
+            }
+        }"#,
+    )?;
+    writer.start_file("modules/child/main.tf", SimpleFileOptions::default())?;
+    writer.write_all(br#"terraform { required_version = "2.0.0" }"#)?;
+    let archive = writer.finish()?;
+
+    let module = tfconfig::load_module_from_archive(
+        Cursor::new(archive.into_inner()),
+        tfconfig::ArchiveFormat::Zip,
+        true,
+    )?;
+
+    assert_eq!(vec!["1.0.0"], module.required_core);
+
+    assert_eq!(1, module.required_providers.len());
+    let required_provider = module.required_providers.get("mycloud");
+    assert!(required_provider.is_some());
+    let required_provider = required_provider.unwrap();
+    assert_eq!("mycorp/mycloud", required_provider.source);
+    assert_eq!(vec!["~> 2.0"], required_provider.version_constraints);
+
+    Ok(())
+}
+
+#[test]
+fn test_load_module_from_file_builtin_provider() -> result::Result<(), Box<dyn Error>> {
+    let file: hcl::Body = hcl::from_str(
+        r#"terraform {
+            required_providers {
+                mycloud = {
+                    source  = "mycorp/mycloud"
+                }
+            }
+        }
+
+        data "terraform_remote_state" "vpc" {
+            backend = "s3"
+        }"#,
+    )?;
+
+    let pathbuf = PathBuf::from("");
+    let mut module = Module::new(pathbuf.clone());
+    tfconfig::load_module_from_file(&pathbuf, file, &mut module)?;
+
+    let data_resource = module.data_resources.get("data.terraform_remote_state.vpc");
+    assert!(data_resource.is_some());
+    let data_resource = data_resource.unwrap();
+    assert_eq!("terraform", data_resource.implied_provider());
+
+    let required_provider = module.required_providers.get("terraform");
+    assert!(required_provider.is_some());
+    let required_provider = required_provider.unwrap();
+    assert_eq!("terraform.io/builtin/terraform", required_provider.source);
+    assert!(required_provider.is_builtin());
+
+    assert!(!module.required_providers["mycloud"].is_builtin());
+
+    assert_eq!(
+        "terraform.io/builtin/terraform",
+        tfconfig::implied_provider_source("terraform")
+    );
+    assert_eq!("hashicorp/aws", tfconfig::implied_provider_source("aws"));
+
+    Ok(())
+}
+
+#[test]
+fn test_load_module_from_file_provider_object_formatting() -> result::Result<(), Box<dyn Error>> {
+    let file: hcl::Body = hcl::from_str(
+        r#"terraform {
+            required_providers {
+                mycloud = {
+                    source  = "mycorp/mycloud",
+                    version = "~> 1.0", # pinned for the 1.x API
+                }
+                othercloud = {
+                    # the source is mirrored internally
+                    source  = "othercorp/othercloud" // trailing comment
+                    version = ">= 2.0" /* inline block comment */
+                }
+            }
+        }"#,
+    )?;
+
+    let pathbuf = PathBuf::from("");
+    let mut module = Module::new(pathbuf.clone());
+    tfconfig::load_module_from_file(&pathbuf, file, &mut module)?;
+
+    assert_eq!(2, module.required_providers.len());
+
+    let required_provider = module.required_providers.get("mycloud");
+    assert!(required_provider.is_some());
+    let required_provider = required_provider.unwrap();
+    assert_eq!("mycorp/mycloud", required_provider.source);
+    assert_eq!(vec!["~> 1.0"], required_provider.version_constraints);
+
+    let required_provider = module.required_providers.get("othercloud");
+    assert!(required_provider.is_some());
+    let required_provider = required_provider.unwrap();
+    assert_eq!("othercorp/othercloud", required_provider.source);
+    assert_eq!(vec![">= 2.0"], required_provider.version_constraints);
+
+    Ok(())
+}
+
+#[cfg(feature = "archive")]
+#[test]
+fn test_load_module_from_archive_max_file_size() -> result::Result<(), Box<dyn Error>> {
+    use std::io::{Cursor, Write};
+    use zip::write::SimpleFileOptions;
+
+    let main_tf = br#"variable "region" {}"#;
+    let large_tf = format!(r#"locals {{ padding = "{}" }}"#, "x".repeat(1024));
+
+    let mut writer = zip::ZipWriter::new(Cursor::new(vec![]));
+    writer.start_file("main.tf", SimpleFileOptions::default())?;
+    writer.write_all(main_tf)?;
+    writer.start_file("large.tf", SimpleFileOptions::default())?;
+    writer.write_all(large_tf.as_bytes())?;
+    let zip_archive = writer.finish()?.into_inner();
+
+    let mut builder = tar::Builder::new(vec![]);
+    for (file_name, contents) in [("main.tf", &main_tf[..]), ("large.tf", large_tf.as_bytes())] {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(contents.len() as u64);
+        header.set_cksum();
+        builder.append_data(&mut header, file_name, contents)?;
+    }
+    let tar_archive = builder.into_inner()?;
+
+    for (format, archive) in [
+        (tfconfig::ArchiveFormat::Zip, &zip_archive),
+        (tfconfig::ArchiveFormat::Tar, &tar_archive),
+    ] {
+        let options = tfconfig::LoadOptions::new().max_file_size(256);
+        let module = tfconfig::load_module_from_archive_with_options(
+            Cursor::new(archive),
+            format,
+            &options,
+        )?;
+        assert!(module.variables.contains_key("region"));
+        assert_eq!(1, module.diagnostics.len());
+        assert_eq!("File too large", module.diagnostics[0].summary);
+
+        let res = tfconfig::load_module_from_archive_with_options(
+            Cursor::new(archive),
+            format,
+            &options.strict(true),
+        );
+        assert!(matches!(
+            res,
+            Err(TfConfigError::FileTooLarge { path, limit: 256, .. }) if path == Path::new("large.tf")
+        ));
+    }
+
+    Ok(())
+}
+
+/// Exercises the core parser in a build without any of the optional integrations, guaranteeing
+/// the minimal build keeps working.
+#[cfg(not(any(
+    feature = "archive",
+    feature = "encoding",
+    feature = "json-schema",
+    feature = "serde",
+    feature = "tracing"
+)))]
+mod minimal {
+    use super::*;
+
+    #[test]
+    fn test_load_module_minimal_build() -> result::Result<(), Box<dyn Error>> {
+        let tmp_dir = TempDir::new("test_load_module_minimal_build")?;
+        let tmp_dir_path = tmp_dir.path();
+        fs::write(
+            tmp_dir_path.join("versions.tf"),
+            r#"terraform {
+                required_version = "1.0.0"
+
+                required_providers {
+                    mycloud = {
+                        source  = "mycorp/mycloud"
+                        version = "~> 1.0"
+                    }
+                }
+            }"#,
+        )?;
+        fs::write(
+            tmp_dir_path.join("main.tf"),
+            r#"resource "mycloud_instance" "web" {}"#,
+        )?;
+
+        let module = tfconfig::load_module(tmp_dir_path, true)?;
+
+        assert_eq!(vec!["1.0.0"], module.required_core);
+        assert_eq!(1, module.required_providers.len());
+        assert_eq!(
+            "mycorp/mycloud",
+            module.required_providers["mycloud"].source
+        );
+        assert!(module
+            .managed_resources
+            .contains_key("mycloud_instance.web"));
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_module_call_resolved_local_path() -> result::Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new("test_module_call_resolved_local_path")?;
+    let tmp_dir_path = tmp_dir.path();
+    let root_path = tmp_dir_path.join("root");
+    fs::create_dir(&root_path)?;
+    fs::create_dir(tmp_dir_path.join("foo"))?;
+    fs::write(
+        root_path.join("main.tf"),
+        r#"module "foo" {
+            source = "../foo"
+        }
+
+        module "consul" {
+            source  = "hashicorp/consul/aws"
+            version = "0.1.0"
+        }"#,
+    )?;
+
+    let module = tfconfig::load_module(&root_path, true)?;
+
+    assert_eq!(2, module.module_calls.len());
+
+    let module_call = module.module_calls.get("foo");
+    assert!(module_call.is_some());
+    let module_call = module_call.unwrap();
+    assert_eq!("../foo", module_call.source);
+    assert_eq!(
+        Some(tmp_dir_path.canonicalize()?.join("foo")),
+        module_call.resolved_local_path(&module.path)
+    );
+
+    let module_call = module.module_calls.get("consul");
+    assert!(module_call.is_some());
+    let module_call = module_call.unwrap();
+    assert_eq!("hashicorp/consul/aws", module_call.source);
+    assert_eq!("0.1.0", module_call.version);
+    assert_eq!(None, module_call.resolved_local_path(&module.path));
+
+    Ok(())
+}
+
+#[test]
+fn test_module_into_result() -> result::Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new("test_module_into_result")?;
+    let tmp_dir_path = tmp_dir.path();
+    fs::write(
+        tmp_dir_path.join("version.tf"),
+        r#"terraform {
+            required_version = "1.0.0"
+        }"#,
+    )?;
+
+    let module = tfconfig::load_module(tmp_dir_path, false)?;
+    assert!(module.diagnostics.is_empty());
+    assert!(module.into_result().is_ok());
+
+    fs::write(tmp_dir_path.join("bad.tf"), "asdsadsadsad")?;
+
+    let module = tfconfig::load_module(tmp_dir_path, false)?;
+    assert_eq!(vec!["1.0.0"], module.required_core);
+    assert_eq!(1, module.diagnostics.len());
+    assert_eq!(DiagnosticSeverity::Error, module.diagnostics[0].severity);
+    assert_eq!(
+        Some(tmp_dir_path.join("bad.tf")),
+        module.diagnostics[0]
+            .pos
+            .as_ref()
+            .map(|pos| pos.filename.clone())
+    );
+
+    let res = module.into_result();
+    assert!(matches!(res, Err(TfConfigError::Multiple(diags)) if diags.len() == 1));
+
+    let mut module = Module::new(PathBuf::from(""));
+    module.diagnostics.push(Diagnostic::new(
+        DiagnosticSeverity::Warning,
+        "Deprecated".to_string(),
+        String::new(),
+    ));
+    assert!(module.into_result().is_ok());
+
+    Ok(())
+}
+
+#[test]
+fn test_load_module_from_file_provider_unknown_keys() -> result::Result<(), Box<dyn Error>> {
+    let file: hcl::Body = hcl::from_str(
+        r#"terraform {
+            required_providers {
+                mycloud = {
+                    source                = "mycorp/mycloud"
+                    version               = "~> 1.0"
+                    mirror                = "https://mirror.example.com"
+                    configuration_aliases = [mycloud.east, mycloud.west]
+                }
+            }
+        }"#,
+    )?;
+
+    let pathbuf = PathBuf::from("");
+    let mut module = Module::new(pathbuf.clone());
+    tfconfig::load_module_from_file(&pathbuf, file, &mut module)?;
+
+    let required_provider = module.required_providers.get("mycloud");
+    assert!(required_provider.is_some());
+    let required_provider = required_provider.unwrap();
+    assert_eq!("mycorp/mycloud", required_provider.source);
+    assert_eq!(vec!["~> 1.0"], required_provider.version_constraints);
+
+    let aliases: Vec<(&str, &str)> = required_provider
+        .configuration_aliases
+        .iter()
+        .map(|alias| (alias.name.as_str(), alias.alias.as_str()))
+        .collect();
+    assert_eq!(vec![("mycloud", "east"), ("mycloud", "west")], aliases);
+
+    Ok(())
+}
+
+#[test]
+fn test_module_counts() -> result::Result<(), Box<dyn Error>> {
+    let file: hcl::Body = hcl::from_str(
+        r#"terraform {
+            required_providers {
+                mycloud = {
+                    source  = "mycorp/mycloud"
+                }
+            }
+        }
+
+        variable "region" {
+            description = "Region to deploy into"
+            default     = "us-east-1"
+        }
+
+        variable "instance_count" {}
+
+        output "instance_ip" {
+            description = "Public IP of the instance"
+            sensitive   = true
+        }
+
+        resource "mycloud_instance" "web" {}
+
+        resource "mycloud_instance" "db" {}
+
+        data "mycloud_image" "ubuntu" {}"#,
+    )?;
+
+    let pathbuf = PathBuf::from("");
+    let mut module = Module::new(pathbuf.clone());
+    tfconfig::load_module_from_file(&pathbuf, file, &mut module)?;
+
+    assert_eq!(1, module.provider_requirement_count());
+    assert_eq!(2, module.variable_count());
+    assert_eq!(1, module.output_count());
+    assert_eq!(2, module.resource_count());
+
+    let variable = module.variables.get("region");
+    assert!(variable.is_some());
+    let variable = variable.unwrap();
+    assert_eq!("Region to deploy into", variable.description);
+    assert_eq!(Some(hcl::Expression::from("us-east-1")), variable.default);
+    assert_eq!(None, module.variables["instance_count"].default);
+
+    let output = module.outputs.get("instance_ip");
+    assert!(output.is_some());
+    let output = output.unwrap();
+    assert_eq!("Public IP of the instance", output.description);
+    assert!(output.sensitive);
+
+    Ok(())
+}
+
+#[test]
+fn test_is_override_file_path_styles() {
+    assert!(tfconfig::is_override_file(Path::new("main_override.tf")));
+    assert!(tfconfig::is_override_file(Path::new("override.tf")));
+    assert!(tfconfig::is_override_file(Path::new(
+        "modules/network/main_override.tf"
+    )));
+
+    assert!(!tfconfig::is_override_file(Path::new("main.tf")));
+    assert!(!tfconfig::is_override_file(Path::new("overrides/main.tf")));
+}
+
+#[cfg(windows)]
+#[test]
+fn test_is_override_file_windows_paths() {
+    assert!(tfconfig::is_override_file(Path::new(
+        r"C:\modules\network\main_override.tf"
+    )));
+    assert!(tfconfig::is_override_file(Path::new(
+        r"C:\modules\override.tf"
+    )));
+
+    assert!(!tfconfig::is_override_file(Path::new(
+        r"C:\modules\override_network\main.tf"
+    )));
+}
+
+#[test]
+fn test_load_module_crlf_override() -> result::Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new("test_load_module_crlf_override")?;
+    let tmp_dir_path = tmp_dir.path();
+    fs::write(
+        tmp_dir_path.join("main_override.tf"),
+        "terraform {\r\n  required_providers {\r\n    mycloud = {\r\n      source  = \"mycorp/mycloud\"\r\n      version = \"~> 2.0\"\r\n    }\r\n  }\r\n}\r\n",
+    )?;
+    fs::write(
+        tmp_dir_path.join("main.tf"),
+        "terraform {\r\n  required_version = \"1.0.0\"\r\n\r\n  required_providers {\r\n    mycloud = {\r\n      source  = \"mycorp/mycloud\"\r\n      version = \"~> 1.0\"\r\n    }\r\n  }\r\n}\r\n",
+    )?;
+
+    let module = tfconfig::load_module(tmp_dir_path, true)?;
+
+    assert_eq!(vec!["1.0.0"], module.required_core);
+    let required_provider = module.required_providers.get("mycloud");
+    assert!(required_provider.is_some());
+    let required_provider = required_provider.unwrap();
+    assert_eq!("mycorp/mycloud", required_provider.source);
+    assert_eq!(vec!["~> 2.0"], required_provider.version_constraints);
+
+    Ok(())
+}
+
+#[test]
+fn test_load_module_from_file_terraform_settings() -> result::Result<(), Box<dyn Error>> {
+    let file: hcl::Body = hcl::from_str(
+        r#"terraform {
+            required_version = "1.0.0"
+            some_future_setting = true
+            experiments = [example]
+        }"#,
+    )?;
+
+    let pathbuf = PathBuf::from("");
+    let mut module = Module::new(pathbuf.clone());
+    tfconfig::load_module_from_file(&pathbuf, file, &mut module)?;
+
+    assert_eq!(vec!["1.0.0"], module.required_core);
+
+    assert_eq!(2, module.terraform_settings.len());
+    assert_eq!(
+        Some(&hcl::Expression::Bool(true)),
+        module.terraform_settings.get("some_future_setting")
+    );
+    assert!(module.terraform_settings.contains_key("experiments"));
+    assert!(!module.terraform_settings.contains_key("required_version"));
+
+    Ok(())
+}
+
+#[test]
+fn test_load_module_from_str() -> result::Result<(), Box<dyn Error>> {
+    let module = tfconfig::load_module_from_str(
+        Path::new("modules/network/versions.tf"),
+        r#"terraform {
+            required_version = "1.0.0"
+        }"#,
+    )?;
+
+    assert_eq!(Path::new("modules/network"), module.path);
+    assert_eq!(vec!["1.0.0"], module.required_core);
+
+    let res = tfconfig::load_module_from_str(Path::new("bad.tf"), "asdsadsadsad");
+    assert!(matches!(res, Err(TfConfigError::Parse(_))));
+
+    let res = tfconfig::load_module_from_str(Path::new("bad.tf"), "resource {}");
+    assert!(matches!(res, Err(TfConfigError::UnexpectedLabels { .. })));
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn test_load_module_non_utf8_file_name_no_panic() -> result::Result<(), Box<dyn Error>> {
+    use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+    let tmp_dir = TempDir::new("test_load_module_non_utf8_file_name_no_panic")?;
+    let tmp_dir_path = tmp_dir.path();
+    fs::write(
+        tmp_dir_path.join(OsStr::from_bytes(b"ma\xFFin.tf")),
+        r#"terraform {
+            required_version = "1.0.0"
+        }"#,
+    )?;
+
+    let module = tfconfig::load_module(tmp_dir_path, false)?;
+    assert!(module.required_core.is_empty());
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn test_load_module_non_utf8_file_name_strict() -> result::Result<(), Box<dyn Error>> {
+    use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+    let tmp_dir = TempDir::new("test_load_module_non_utf8_file_name_strict")?;
+    let tmp_dir_path = tmp_dir.path();
+    let file_path = tmp_dir_path.join(OsStr::from_bytes(b"ma\xFFin.tf"));
+    fs::write(&file_path, "")?;
+
+    let res = tfconfig::load_module(tmp_dir_path, true);
+    assert!(matches!(res, Err(TfConfigError::NonUtf8Path(path)) if path == file_path));
+
+    let tmp_dir = TempDir::new("test_load_module_non_utf8_extension_strict")?;
+    let tmp_dir_path = tmp_dir.path();
+    let file_path = tmp_dir_path.join(OsStr::from_bytes(b"main.t\xFFf"));
+    fs::write(&file_path, "")?;
+
+    let module = tfconfig::load_module(tmp_dir_path, false)?;
+    assert!(module.diagnostics.is_empty());
+
+    let res = tfconfig::load_module(tmp_dir_path, true);
+    assert!(matches!(res, Err(TfConfigError::NonUtf8Path(path)) if path == file_path));
+
+    Ok(())
+}
+
+#[test]
+fn test_module_provider_source_map() -> result::Result<(), Box<dyn Error>> {
+    let file: hcl::Body = hcl::from_str(
+        r#"terraform {
+            required_providers {
+                mycloud = {
+                    source  = "mycorp/mycloud"
+                }
+                aws = {
+                    version = "~> 5.0"
+                }
+                internal = {
+                    source  = "example.com/myorg/internal"
+                }
+            }
+        }
+
+        data "terraform_remote_state" "vpc" {}"#,
+    )?;
+
+    let pathbuf = PathBuf::from("");
+    let mut module = Module::new(pathbuf.clone());
+    tfconfig::load_module_from_file(&pathbuf, file, &mut module)?;
+
+    let source_map = module.provider_source_map();
+    assert_eq!(4, source_map.len());
+    assert_eq!(
+        "registry.terraform.io/mycorp/mycloud",
+        source_map["mycloud"]
+    );
+    assert_eq!("registry.terraform.io/hashicorp/aws", source_map["aws"]);
+    assert_eq!("example.com/myorg/internal", source_map["internal"]);
+    assert_eq!("terraform.io/builtin/terraform", source_map["terraform"]);
+
+    let source_parts = module.required_providers["mycloud"].source_parts();
+    assert_eq!(
+        Some(tfconfig::SourceAddr::new(
+            "registry.terraform.io".to_string(),
+            "mycorp".to_string(),
+            "mycloud".to_string()
+        )),
+        source_parts
+    );
+    assert_eq!(None, module.required_providers["aws"].source_parts());
+
+    Ok(())
+}
+
+#[test]
+fn test_module_providers_by_source() -> result::Result<(), Box<dyn Error>> {
+    let module = tfconfig::load_module_from_str(
+        Path::new("main.tf"),
+        r#"terraform {
+            required_providers {
+                aws = {
+                    source = "hashicorp/aws"
+                }
+                aws-legacy = {
+                    source = "registry.terraform.io/hashicorp/aws"
+                }
+                google = {
+                    source = "hashicorp/google"
+                }
+            }
+        }"#,
+    )?;
+
+    let by_source = module.providers_by_source();
+    assert_eq!(2, by_source.len());
+    assert_eq!(
+        vec!["aws", "aws-legacy"],
+        by_source["registry.terraform.io/hashicorp/aws"]
+    );
+    assert_eq!(
+        vec!["google"],
+        by_source["registry.terraform.io/hashicorp/google"]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_load_module_state_blocks_in_override_files() -> result::Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new("test_load_module_state_blocks_in_override_files")?;
+    let tmp_dir_path = tmp_dir.path();
+    fs::write(
+        tmp_dir_path.join("imports.tf"),
+        r#"import {
+            to = aws_instance.web
+            id = "i-12345678"
+        }
+
+        moved {
+            from = aws_instance.old
+            to   = aws_instance.web
+        }"#,
+    )?;
+    fs::write(
+        tmp_dir_path.join("imports_override.tf"),
+        r#"import {
+            to = aws_instance.db
+            id = "i-87654321"
+        }
+
+        removed {
+            from = aws_instance.legacy
+        }"#,
+    )?;
+
+    let module = tfconfig::load_module(tmp_dir_path, true)?;
+
+    let import_targets: Vec<&str> = module
+        .import_blocks
+        .iter()
+        .map(|import| import.to.as_str())
+        .collect();
+    assert_eq!(vec!["aws_instance.web", "aws_instance.db"], import_targets);
+    assert_eq!(
+        Some(hcl::Expression::from("i-12345678")),
+        module.import_blocks[0].id
+    );
+
+    assert_eq!(1, module.moved_blocks.len());
+    assert_eq!("aws_instance.old", module.moved_blocks[0].from);
+    assert_eq!("aws_instance.web", module.moved_blocks[0].to);
+
+    assert_eq!(1, module.removed_blocks.len());
+    assert_eq!("aws_instance.legacy", module.removed_blocks[0].from);
+
+    Ok(())
+}
+
+#[test]
+fn test_module_provider_constraints_for() -> result::Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new("test_module_provider_constraints_for")?;
+    let tmp_dir_path = tmp_dir.path();
+    fs::write(
+        tmp_dir_path.join("versions.tf"),
+        r#"terraform {
+            required_providers {
+                aws = {
+                    source  = "hashicorp/aws"
+                    version = ">= 4.0"
+                }
+            }
+        }"#,
+    )?;
+    fs::write(
+        tmp_dir_path.join("provider.tf"),
+        r#"provider "aws" {
+            region  = "us-east-1"
+            version = "< 6.0"
+        }
+
+        provider "aws" {
+            alias  = "east"
+            region = "us-east-2"
+        }
+
+        provider "google" {
+            version = "~> 5.0"
+        }"#,
+    )?;
+
+    let module = tfconfig::load_module(tmp_dir_path, true)?;
+
+    assert_eq!(3, module.provider_configs.len());
+    let provider_config = module.provider_configs.get("aws");
+    assert!(provider_config.is_some());
+    let provider_config = provider_config.unwrap();
+    assert_eq!("", provider_config.alias);
+    assert_eq!(Some("< 6.0".to_string()), provider_config.version);
+
+    let provider_config = module.provider_configs.get("aws.east");
+    assert!(provider_config.is_some());
+    let provider_config = provider_config.unwrap();
+    assert_eq!("east", provider_config.alias);
+    assert_eq!(None, provider_config.version);
+
+    assert_eq!("hashicorp/aws", module.required_providers["aws"].source);
+    // The inline version is ignored in favour of required_providers, as when loading.
+    assert_eq!(vec![">= 4.0"], module.provider_constraints_for("aws"));
+    assert_eq!(
+        vec![">= 4.0"],
+        module.required_providers["aws"].version_constraints
+    );
+    assert_eq!(vec!["~> 5.0"], module.provider_constraints_for("google"));
+    assert!(module.provider_constraints_for("azurerm").is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_load_module_from_file_version_constraints_order() -> result::Result<(), Box<dyn Error>> {
+    let file: hcl::Body = hcl::from_str(
+        r#"terraform {
+            required_providers {
+                mycloud = {
+                    source  = "mycorp/mycloud"
+                    version = ">= 1.0, < 2.0, >= 1.0"
+                }
+            }
+        }
+
+        provider "mycloud" {
+            version = "< 2.0, != 1.5.0"
+        }"#,
+    )?;
+
+    let pathbuf = PathBuf::from("");
+    let mut module = Module::new(pathbuf.clone());
+    tfconfig::load_module_from_file(&pathbuf, file, &mut module)?;
+
+    assert_eq!(
+        vec![">= 1.0", "< 2.0"],
+        module.required_providers["mycloud"].version_constraints
+    );
+    assert_eq!(
+        vec![">= 1.0", "< 2.0"],
+        module.provider_constraints_for("mycloud")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_module_suspicious_provider_sources() -> result::Result<(), Box<dyn Error>> {
+    let file: hcl::Body = hcl::from_str(
+        r#"terraform {
+            required_providers {
+                aws = {
+                    source  = "hashicorp/awss"
+                }
+                google = {
+                    source  = "registry.terraform.io/hashicorp/google"
+                }
+                random = {}
+                mycloud = {
+                    source  = "mycorp/mycloud"
+                }
+            }
+        }
+
+        data "terraform_remote_state" "vpc" {}"#,
+    )?;
+
+    let pathbuf = PathBuf::from("");
+    let mut module = Module::new(pathbuf.clone());
+    tfconfig::load_module_from_file(&pathbuf, file, &mut module)?;
+
+    let known: HashSet<String> = ["hashicorp/aws", "hashicorp/google", "hashicorp/random"]
+        .into_iter()
+        .map(String::from)
+        .collect();
+
+    assert_eq!(
+        vec![
+            ("aws".to_string(), Some("hashicorp/aws".to_string())),
+            ("mycloud".to_string(), None),
+        ],
+        module.suspicious_provider_sources(&known)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_load_module_from_file_resource_provider() -> result::Result<(), Box<dyn Error>> {
+    let file: hcl::Body = hcl::from_str(
+        r#"resource "aws_instance" "east" {
+            provider = aws.east
+        }
+
+        resource "aws_instance" "default" {
+            provider = aws
+        }
+
+        resource "aws_instance" "implied" {}
+
+        resource "google_compute_instance" "beta" {
+            provider = google-beta
+        }"#,
+    )?;
+
+    let pathbuf = PathBuf::from("");
+    let mut module = Module::new(pathbuf.clone());
+    tfconfig::load_module_from_file(&pathbuf, file, &mut module)?;
+
+    let provider = module.managed_resources["aws_instance.east"]
+        .provider
+        .as_ref();
+    assert!(provider.is_some());
+    let provider = provider.unwrap();
+    assert_eq!("aws", provider.name);
+    assert_eq!("east", provider.alias);
+
+    let provider = module.managed_resources["aws_instance.default"]
+        .provider
+        .as_ref();
+    assert!(provider.is_some());
+    let provider = provider.unwrap();
+    assert_eq!("aws", provider.name);
+    assert_eq!("", provider.alias);
+
+    assert!(module.managed_resources["aws_instance.implied"]
+        .provider
+        .is_none());
+
+    assert!(module.required_providers.contains_key("google-beta"));
+    assert!(!module.required_providers.contains_key("google"));
+
+    let file: hcl::Body = hcl::from_str(
+        r#"resource "aws_instance" "bad" {
+            provider = "aws.east"
+        }"#,
+    )?;
+    let result = tfconfig::load_module_from_file(&pathbuf, file, &mut module);
+    assert!(matches!(
+        result,
+        Err(TfConfigError::UnexpectedExpr { attribute_key, .. }) if attribute_key == "provider"
+    ));
+
+    Ok(())
+}
+
+#[cfg(feature = "encoding")]
+#[test]
+fn test_load_module_with_options_encoding() -> result::Result<(), Box<dyn Error>> {
+    use tfconfig::LoadOptions;
+
+    let tmp_dir = TempDir::new("test_load_module_with_options_encoding")?;
+    let tmp_dir_path = tmp_dir.path();
+    let (file_contents, _, _) = encoding_rs::WINDOWS_1252.encode(
+        r#"# Configuration gérée par l'équipe réseau
+        terraform {
+            required_version = "1.0.0"
+        }"#,
+    );
+    fs::write(tmp_dir_path.join("version.tf"), file_contents)?;
+
+    let res = tfconfig::load_module(tmp_dir_path, true);
+    assert!(matches!(res, Err(TfConfigError::Io(_))));
+
+    let options = LoadOptions::new()
+        .strict(true)
+        .encoding(encoding_rs::WINDOWS_1252);
+    let module = tfconfig::load_module_with_options(tmp_dir_path, &options)?;
+    assert_eq!(vec!["1.0.0"], module.required_core);
+
+    Ok(())
+}
+
+#[test]
+fn test_module_summary() -> result::Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new("test_module_summary")?;
+    let tmp_dir_path = tmp_dir.path();
+    fs::write(
+        tmp_dir_path.join("main.tf"),
+        r#"terraform {
+            required_version = "1.0.0"
+
+            required_providers {
+                mycloud = {
+                    source  = "mycorp/mycloud"
+                    version = "~> 1.0"
+                }
+            }
+        }
+
+        variable "region" {}
+
+        resource "mycloud_instance" "web" {}
+
+        resource "mycloud_instance" "db" {}
+
+        data "mycloud_image" "ubuntu" {}"#,
+    )?;
+
+    let module = tfconfig::load_module(tmp_dir_path, true)?;
+    let summary = tfconfig::ModuleSummary::from(&module);
+
+    assert_eq!(vec!["1.0.0"], summary.required_core);
+    assert_eq!("mycorp/mycloud", summary.providers["mycloud"].source);
+    assert_eq!(vec!["region"], summary.variables);
+    assert_eq!(Some(&2), summary.resource_types.get("mycloud_instance"));
+    assert_eq!(Some(&1), summary.data_resource_types.get("mycloud_image"));
+
+    Ok(())
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_module_summary_serialize() -> result::Result<(), Box<dyn Error>> {
+    let module = tfconfig::load_module_from_str(
+        Path::new("main.tf"),
+        r#"terraform {
+            required_providers {
+                mycloud = {
+                    source  = "mycorp/mycloud"
+                    version = "~> 1.0"
+                }
+            }
+        }
+
+        resource "mycloud_instance" "web" {}"#,
+    )?;
+    let summary = tfconfig::ModuleSummary::from(&module);
+
+    let json = serde_json::to_value(&summary)?;
+    assert_eq!(
+        serde_json::json!({
+            "source": "mycorp/mycloud",
+            "version_constraints": ["~> 1.0"],
+        }),
+        json["providers"]["mycloud"]
+    );
+    assert_eq!(
+        serde_json::json!(1),
+        json["resource_types"]["mycloud_instance"]
+    );
+
+    let deserialized: tfconfig::ModuleSummary = serde_json::from_value(json)?;
+    assert_eq!(summary, deserialized);
+
+    Ok(())
+}
+
+#[test]
+fn test_inline_provider_version_precedence() -> result::Result<(), Box<dyn Error>> {
+    let module = tfconfig::load_module_from_str(
+        Path::new("main.tf"),
+        r#"terraform {
+            required_providers {
+                aws = {
+                    source  = "hashicorp/aws"
+                    version = ">= 4.0"
+                }
+            }
+        }
+
+        provider "aws" {
+            version = "~> 3.0"
+        }
+
+        provider "google" {
+            version = "~> 5.0"
+        }"#,
+    )?;
+
+    let aws = module.required_providers.get("aws");
+    assert!(aws.is_some());
+    let aws = aws.unwrap();
+    assert_eq!(vec![">= 4.0"], aws.version_constraints);
+
+    let google = module.required_providers.get("google");
+    assert!(google.is_some());
+    let google = google.unwrap();
+    assert_eq!(vec!["~> 5.0"], google.version_constraints);
+
+    assert_eq!(1, module.diagnostics.len());
+    let diagnostic = &module.diagnostics[0];
+    assert_eq!(DiagnosticSeverity::Warning, diagnostic.severity);
+    assert!(diagnostic.detail.contains("~> 3.0"));
+
+    Ok(())
+}
+
+#[test]
+fn test_touched_files() -> result::Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new("test_touched_files")?;
+    let tmp_dir_path = tmp_dir.path();
+    fs::write(tmp_dir_path.join("main.tf"), r#"variable "region" {}"#)?;
+    fs::write(tmp_dir_path.join("outputs.tf"), r#"output "id" {}"#)?;
+    fs::write(tmp_dir_path.join("empty.tf"), "# nothing here")?;
+
+    let module = tfconfig::load_module(tmp_dir_path, true)?;
+
+    let mut touched_files = module.touched_files().to_vec();
+    touched_files.sort();
+    assert_eq!(
+        vec![
+            tmp_dir_path.join("main.tf"),
+            tmp_dir_path.join("outputs.tf")
+        ],
+        touched_files
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_resource_provisioners() -> result::Result<(), Box<dyn Error>> {
+    let module = tfconfig::load_module_from_str(
+        Path::new("main.tf"),
+        r#"resource "aws_instance" "web" {
+            provisioner "local-exec" {
+                command = "echo ${self.private_ip}"
+            }
+
+            provisioner "remote-exec" {
+                inline = ["puppet apply"]
+            }
+        }"#,
+    )?;
+
+    let resource = module.managed_resources.get("aws_instance.web");
+    assert!(resource.is_some());
+    let resource = resource.unwrap();
+    assert_eq!(vec!["local-exec", "remote-exec"], resource.provisioners);
+
+    Ok(())
+}
+
+#[test]
+fn test_load_module_tree_max_depth() -> result::Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new("test_load_module_tree_max_depth")?;
+    let root = tmp_dir.path();
+    let child = root.join("child");
+    let grandchild = child.join("grandchild");
+    fs::create_dir_all(&grandchild)?;
+    fs::write(
+        root.join("main.tf"),
+        r#"module "child" { source = "./child" }"#,
+    )?;
+    fs::write(
+        child.join("main.tf"),
+        r#"module "grandchild" { source = "./grandchild" }"#,
+    )?;
+    fs::write(grandchild.join("main.tf"), r#"variable "region" {}"#)?;
+
+    let tree = tfconfig::load_module_tree(root, &tfconfig::LoadOptions::new())?;
+    let child_tree = tree.children.get("child");
+    assert!(child_tree.is_some());
+    assert!(child_tree.unwrap().children.contains_key("grandchild"));
+
+    let options = tfconfig::LoadOptions::new().max_depth(1);
+    let tree = tfconfig::load_module_tree(root, &options)?;
+    assert!(tree.module.diagnostics.is_empty());
+
+    let child_tree = tree.children.get("child");
+    assert!(child_tree.is_some());
+    let child_tree = child_tree.unwrap();
+    assert!(child_tree.children.is_empty());
+    assert_eq!(1, child_tree.module.diagnostics.len());
+    assert_eq!(
+        DiagnosticSeverity::Warning,
+        child_tree.module.diagnostics[0].severity
+    );
+    assert!(child_tree.module.diagnostics[0]
+        .detail
+        .contains("grandchild"));
+
+    Ok(())
+}
+
+#[test]
+fn test_to_graphviz() -> result::Result<(), Box<dyn Error>> {
+    let module = tfconfig::load_module_from_str(
+        Path::new("main.tf"),
+        r#"resource "aws_vpc" "main" {}
+
+        resource "aws_instance" "web" {
+            depends_on = [aws_vpc.main, module.network]
+        }
+
+        module "network" {
+            source = "./network"
+        }"#,
+    )?;
+
+    let web = module.managed_resources.get("aws_instance.web");
+    assert!(web.is_some());
+    assert_eq!(
+        vec!["aws_vpc.main", "module.network"],
+        web.unwrap().depends_on
+    );
+
+    let dot = module.to_graphviz();
+    assert!(dot.starts_with("digraph {\n"));
+    assert!(dot.contains("  \"module.network\";\n"));
+    assert!(dot.contains("  \"aws_instance.web\" -> \"aws_vpc.main\";\n"));
+    assert!(dot.contains("  \"aws_instance.web\" -> \"module.network\";\n"));
+
+    Ok(())
+}
+
+#[test]
+fn test_is_override_file_json() {
+    assert!(tfconfig::is_override_file(Path::new(
+        "main_override.tf.json"
+    )));
+    assert!(tfconfig::is_override_file(Path::new("override.tf.json")));
+
+    assert!(!tfconfig::is_override_file(Path::new("main.tf.json")));
+    assert!(!tfconfig::is_override_file(Path::new(
+        "override.tf.json.bak"
+    )));
+}
+
+#[test]
+fn test_variable_is_required() -> result::Result<(), Box<dyn Error>> {
+    let module = tfconfig::load_module_from_str(
+        Path::new("variables.tf"),
+        r#"variable "region" {}
+
+        variable "tags" {
+            default = null
+        }"#,
+    )?;
+
+    let region = module.variables.get("region");
+    assert!(region.is_some());
+    assert!(region.unwrap().is_required());
+
+    let tags = module.variables.get("tags");
+    assert!(tags.is_some());
+    assert!(!tags.unwrap().is_required());
+
+    Ok(())
+}
+
+#[test]
+fn test_strict_providers() -> result::Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new("test_strict_providers")?;
+    let tmp_dir_path = tmp_dir.path();
+    fs::write(
+        tmp_dir_path.join("main.tf"),
+        r#"terraform {
+            required_providers {
+                aws = {
+                    source = "hashicorp/aws"
+                }
+                mycloud = {
+                    source = "just-a-name"
+                }
+                google = {
+                    configuration_aliases = [google.west]
+                }
+                random = {}
+            }
+        }"#,
+    )?;
+
+    let module = tfconfig::load_module(tmp_dir_path, true)?;
+    assert_eq!(1, module.diagnostics.len());
+    assert_eq!(DiagnosticSeverity::Warning, module.diagnostics[0].severity);
+    assert!(module.diagnostics[0].detail.contains("mycloud"));
+
+    let options = tfconfig::LoadOptions::new().strict_providers(true);
+    let res = tfconfig::load_module_with_options(tmp_dir_path, &options);
+    assert!(matches!(
+        res,
+        Err(TfConfigError::InvalidProviderSource { name, source_addr })
+            if name == "mycloud" && source_addr == "just-a-name"
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_strict_providers_without_source() -> result::Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new("test")?;
+    fs::write(
+        tmp_dir.path().join("main.tf"),
+        r#"terraform {
+            required_providers {
+                google = {
+                    configuration_aliases = [google.west]
+                }
+            }
+        }"#,
+    )?;
+
+    let options = tfconfig::LoadOptions::new()
+        .strict(true)
+        .strict_providers(true);
+    let module = tfconfig::load_module_with_options(tmp_dir.path(), &options)?;
+
+    assert!(module.diagnostics.is_empty());
+    assert_eq!(
+        Some("registry.terraform.io/hashicorp/google"),
+        module
+            .provider_source_map()
+            .get("google")
+            .map(String::as_str)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_load_module_rich() -> result::Result<(), Box<dyn Error>> {
+    use hcl::edit::Decorate;
+
+    let tmp_dir = TempDir::new("test_load_module_rich")?;
+    let tmp_dir_path = tmp_dir.path();
+    fs::write(
+        tmp_dir_path.join("main.tf"),
+        r#"# Owned by the networking team
+variable "region" {}"#,
+    )?;
+
+    let rich = tfconfig::load_module_rich(tmp_dir_path, true)?;
+    assert!(rich.module.variables.contains_key("region"));
+
+    let document = rich.documents.get(&tmp_dir_path.join("main.tf"));
+    assert!(document.is_some());
+    let document = document.unwrap();
+
+    let variable = document.iter().next();
+    assert!(variable.is_some());
+    let prefix = variable.unwrap().decor().prefix();
+    assert!(prefix.is_some_and(|prefix| prefix.contains("# Owned by the networking team")));
+
+    let body = hcl::parse(&fs::read_to_string(tmp_dir_path.join("main.tf"))?)?;
+    assert!(!hcl::format::to_string(&body)?.contains("networking team"));
+
+    Ok(())
+}
+
+#[test]
+fn test_provider_requirement_matches_version() -> result::Result<(), Box<dyn Error>> {
+    let req =
+        tfconfig::ProviderRequirement::new("hashicorp/aws".to_string(), vec!["~> 1.0".to_string()]);
+    assert!(req.matches_version("1.5.0")?);
+    assert!(!req.matches_version("2.0.0")?);
+
+    let req = tfconfig::ProviderRequirement::new(
+        "hashicorp/aws".to_string(),
+        vec![">= 1.2, < 1.4".to_string(), "!= 1.3.1".to_string()],
+    );
+    assert!(req.matches_version("1.3.0")?);
+    assert!(!req.matches_version("1.3.1")?);
+    assert!(!req.matches_version("1.4.0")?);
+
+    let req =
+        tfconfig::ProviderRequirement::new("hashicorp/aws".to_string(), vec!["~> one".to_string()]);
+    assert!(matches!(
+        req.matches_version("1.0.0"),
+        Err(TfConfigError::InvalidVersionConstraint(_))
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_version_equality_ignores_omitted_segments() -> result::Result<(), Box<dyn Error>> {
+    for constraint in ["= 1.2", "1.2"] {
+        let req = tfconfig::ProviderRequirement::default().add_version_constraint(constraint);
+        assert!(req.matches_version("1.2.0")?);
+        assert!(!req.matches_version("1.2.1")?);
+    }
+
+    let req = tfconfig::ProviderRequirement::default().add_version_constraint("!= 1.2");
+    assert!(!req.matches_version("1.2.0")?);
+    assert!(req.matches_version("1.2.1")?);
+
+    Ok(())
+}
+
+#[test]
+fn test_version_constraint_trailing_comma() {
+    let req = tfconfig::ProviderRequirement::new(
+        "hashicorp/aws".to_string(),
+        vec![">= 1.0, ".to_string()],
+    );
+    let res = req.matches_version("1.0.0");
+    assert!(matches!(
+        &res,
+        Err(TfConfigError::EmptyVersionConstraint(constraints)) if constraints == ">= 1.0, "
+    ));
+    assert!(res.unwrap_err().to_string().contains("trailing comma"));
+}
+
+#[test]
+fn test_terraform_nested_blocks() -> result::Result<(), Box<dyn Error>> {
+    let module = tfconfig::load_module_from_str(
+        Path::new("main.tf"),
+        r#"terraform {
+            required_providers {
+                aws = {
+                    source = "hashicorp/aws"
+                }
+            }
+
+            cloud {
+                organization = "example"
+            }
+        }"#,
+    )?;
+
+    assert_eq!(1, module.terraform_nested_blocks.len());
+    let block = &module.terraform_nested_blocks[0];
+    assert_eq!("cloud", block.identifier());
+    assert!(block
+        .body()
+        .attributes()
+        .any(|attr| attr.key() == "organization"));
+
+    Ok(())
+}
+
+#[test]
+fn test_load_module_read_threads() -> result::Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new("test_load_module_read_threads")?;
+    let tmp_dir_path = tmp_dir.path();
+    for i in 0..10 {
+        fs::write(
+            tmp_dir_path.join(format!("file{i}.tf")),
+            format!(
+                r#"variable "var{i}" {{}}
+
+                resource "aws_instance" "web{i}" {{}}"#
+            ),
+        )?;
+    }
+    fs::write(
+        tmp_dir_path.join("override.tf"),
+        r#"terraform { required_version = ">= 1.0" }"#,
+    )?;
+
+    let sequential = tfconfig::load_module(tmp_dir_path, true)?;
+    let options = tfconfig::LoadOptions::new().strict(true).read_threads(3);
+    let threaded = tfconfig::load_module_with_options(tmp_dir_path, &options)?;
+
+    assert_eq!(
+        tfconfig::ModuleSummary::from(&sequential),
+        tfconfig::ModuleSummary::from(&threaded)
+    );
+    assert_eq!(sequential.touched_files(), threaded.touched_files());
+
+    Ok(())
+}
+
+#[test]
+fn test_load_module_empty_files() -> result::Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new("test_load_module_empty_files")?;
+    let tmp_dir_path = tmp_dir.path();
+    fs::write(tmp_dir_path.join("empty.tf"), "")?;
+    fs::write(tmp_dir_path.join("whitespace.tf"), " \n\t\r\n  \n")?;
+
+    for strict in [true, false] {
+        let module = tfconfig::load_module(tmp_dir_path, strict)?;
+        assert!(module.diagnostics.is_empty());
+        assert!(module.touched_files().is_empty());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_provider_ref_hash() {
+    let mut aliases = HashSet::new();
+    aliases.insert(tfconfig::ProviderRef::new(
+        "aws".to_string(),
+        "west".to_string(),
+    ));
+    aliases.insert(tfconfig::ProviderRef::new(
+        "aws".to_string(),
+        "west".to_string(),
+    ));
+    assert_eq!(1, aliases.len());
+
+    aliases.insert(tfconfig::ProviderRef::new(
+        "aws".to_string(),
+        "east".to_string(),
+    ));
+    assert_eq!(2, aliases.len());
+}
+
+#[test]
+fn test_provider_requirement_pinning() {
+    let pinning = |constraints: &[&str]| {
+        tfconfig::ProviderRequirement::new(
+            "hashicorp/aws".to_string(),
+            constraints.iter().map(|c| c.to_string()).collect(),
+        )
+        .pinning()
+    };
+
+    assert_eq!(tfconfig::Pinning::Exact, pinning(&["= 1.2.3"]));
+    assert_eq!(tfconfig::Pinning::Exact, pinning(&["1.2.3"]));
+    assert_eq!(tfconfig::Pinning::Range, pinning(&["~> 1.2.3"]));
+    assert_eq!(tfconfig::Pinning::Range, pinning(&[">= 1.0", "< 2.0"]));
+    assert_eq!(tfconfig::Pinning::Loose, pinning(&[">= 1.0"]));
+    assert_eq!(tfconfig::Pinning::Unpinned, pinning(&[]));
+    assert_eq!(tfconfig::Pinning::Unpinned, pinning(&["!= 1.5.0"]));
+}
+
+#[test]
+fn test_variable_non_literal_bools() -> result::Result<(), Box<dyn Error>> {
+    let module = tfconfig::load_module_from_str(
+        Path::new("variables.tf"),
+        r#"variable "password" {
+            sensitive = true
+            nullable  = false
+        }
+
+        variable "token" {
+            sensitive = local.is_sensitive
+        }"#,
+    )?;
+
+    let password = module.variables.get("password");
+    assert!(password.is_some());
+    let password = password.unwrap();
+    assert_eq!(Some(true), password.sensitive);
+    assert_eq!(Some(false), password.nullable);
+    assert_eq!(None, password.ephemeral);
+
+    let token = module.variables.get("token");
+    assert!(token.is_some());
+    assert_eq!(None, token.unwrap().sensitive);
+
+    assert_eq!(1, module.diagnostics.len());
+    assert_eq!(DiagnosticSeverity::Warning, module.diagnostics[0].severity);
+
+    Ok(())
+}
+
+#[test]
+fn test_provider_aliases() -> result::Result<(), Box<dyn Error>> {
+    let module = tfconfig::load_module_from_str(
+        Path::new("main.tf"),
+        r#"terraform {
+            required_providers {
+                aws = {
+                    source                = "hashicorp/aws"
+                    configuration_aliases = [aws.west, aws.east]
+                }
+            }
+        }
+
+        provider "aws" {
+            alias = "east"
+        }
+
+        provider "aws" {}"#,
+    )?;
+
+    let aliases = module.provider_aliases();
+    assert_eq!(
+        vec![
+            tfconfig::ProviderRef::new("aws".to_string(), "east".to_string()),
+            tfconfig::ProviderRef::new("aws".to_string(), "west".to_string()),
+        ],
+        aliases
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_required_core_origins() -> result::Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new("test_required_core_origins")?;
+    let tmp_dir_path = tmp_dir.path();
+    fs::write(
+        tmp_dir_path.join("versions.tf"),
+        r#"terraform { required_version = ">= 1.5" }"#,
+    )?;
+    fs::write(tmp_dir_path.join("main.tf"), r#"variable "region" {}"#)?;
+
+    let module = tfconfig::load_module(tmp_dir_path, true)?;
+    assert_eq!(vec![">= 1.5"], module.required_core);
+
+    let origins: Vec<(&str, &Path)> = module.required_core_with_origins().collect();
+    assert_eq!(
+        vec![(">= 1.5", tmp_dir_path.join("versions.tf").as_path())],
+        origins
+    );
+
+    Ok(())
+}
+
+#[cfg(feature = "json-schema")]
+#[test]
+fn test_variables_json_schema() -> result::Result<(), Box<dyn Error>> {
+    let module = tfconfig::load_module_from_str(
+        Path::new("variables.tf"),
+        r#"variable "region" {
+            type        = string
+            description = "Region to deploy to"
+        }
+
+        variable "instance" {
+            type = object({
+                name = string
+                size = optional(number, 1)
+            })
+            default = {
+                name = "web"
+            }
+        }"#,
+    )?;
+
+    let schema = module.variables_json_schema();
+    assert_eq!(serde_json::json!(["region"]), schema["required"]);
+    assert_eq!(
+        serde_json::json!({
+            "type": "string",
+            "description": "Region to deploy to",
+        }),
+        schema["properties"]["region"]
+    );
+
+    let instance = &schema["properties"]["instance"];
+    assert_eq!(serde_json::json!("object"), instance["type"]);
+    assert_eq!(serde_json::json!(["name"]), instance["required"]);
+    assert_eq!(
+        serde_json::json!({ "type": "number" }),
+        instance["properties"]["size"]
+    );
+    assert_eq!(serde_json::json!({ "name": "web" }), instance["default"]);
+
+    Ok(())
+}
+
+#[cfg(feature = "json-schema")]
+#[test]
+fn test_variables_json_schema_legacy_types() -> result::Result<(), Box<dyn Error>> {
+    let module = tfconfig::load_module_from_str(
+        Path::new("variables.tf"),
+        r#"variable "name" {
+            type = "string"
+        }
+
+        variable "zones" {
+            type = "list"
+        }"#,
+    )?;
+
+    let schema = module.variables_json_schema();
+    assert_eq!(
+        serde_json::json!({ "type": "string" }),
+        schema["properties"]["name"]
+    );
+    assert_eq!(
+        serde_json::json!({ "type": "array", "items": {} }),
+        schema["properties"]["zones"]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_error_is_recoverable() -> result::Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new("test_error_is_recoverable")?;
+    let tmp_dir_path = tmp_dir.path();
+    let file_path = tmp_dir_path.join("main.tf");
+    fs::write(&file_path, "variable {")?;
+
+    let res = tfconfig::load_module(tmp_dir_path, true);
+    assert!(matches!(&res, Err(e @ TfConfigError::Parse(_)) if e.is_recoverable()));
+
+    let res = tfconfig::load_module(&file_path, true);
+    assert!(
+        matches!(&res, Err(e @ TfConfigError::NotADirectory(path)) if !e.is_recoverable() && path == &file_path)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_required_providers_computed_source() -> result::Result<(), Box<dyn Error>> {
+    let module = tfconfig::load_module_from_str(
+        Path::new("main.tf"),
+        r#"terraform {
+            required_providers {
+                aws = {
+                    source = "${local.host}/hashicorp/aws"
+                }
+                google = {
+                    source = "hashicorp/google"
+                }
+            }
+        }"#,
+    )?;
+
+    let aws = module.required_providers.get("aws");
+    assert!(aws.is_some());
+    let aws = aws.unwrap();
+    assert!(aws.source_is_computed);
+    assert_eq!("${local.host}/hashicorp/aws", aws.source);
+
+    let google = module.required_providers.get("google");
+    assert!(google.is_some());
+    let google = google.unwrap();
+    assert!(!google.source_is_computed);
+    assert_eq!("hashicorp/google", google.source);
+
+    assert!(module.diagnostics.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_discover_modules() -> result::Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new("test_discover_modules")?;
+    let root = tmp_dir.path();
+    let network = root.join("modules").join("network");
+    let docs = root.join("docs");
+    let cached = root.join(".terraform").join("modules").join("vpc");
+    for dir in [&network, &docs, &cached] {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(root.join("main.tf"), r#"module "network" {}"#)?;
+    fs::write(network.join("main.tf"), r#"variable "cidr" {}"#)?;
+    fs::write(docs.join("README.md"), "# Docs")?;
+    fs::write(cached.join("main.tf"), r#"variable "cidr" {}"#)?;
+
+    let modules = tfconfig::discover_modules(root)?;
+    assert_eq!(vec![root.to_path_buf(), network], modules);
+
+    Ok(())
+}
+
+#[test]
+fn test_module_call_for_each() -> result::Result<(), Box<dyn Error>> {
+    let module = tfconfig::load_module_from_str(
+        Path::new("main.tf"),
+        r#"module "network" {
+            source   = "./network"
+            for_each = var.regions
+        }
+
+        module "dns" {
+            source = "./dns"
+        }"#,
+    )?;
+
+    let network = module.module_calls.get("network");
+    assert!(network.is_some());
+    let network = network.unwrap();
+    assert!(network.is_repeated());
+    assert!(network.count.is_none());
+    assert_eq!(
+        Some("var.regions".to_string()),
+        network.for_each.as_ref().map(ToString::to_string)
+    );
+
+    let dns = module.module_calls.get("dns");
+    assert!(dns.is_some());
+    assert!(!dns.unwrap().is_repeated());
+
+    Ok(())
+}
+
+#[test]
+fn test_into_inspect() -> result::Result<(), Box<dyn Error>> {
+    let module = tfconfig::load_module_from_str(
+        Path::new("main.tf"),
+        r#"variable "region" {
+            type = string
+        }
+
+        resource "aws_instance" "web" {
+            provider = aws.west
+        }
+
+        data "aws_ami" "ubuntu" {}"#,
+    )?;
+
+    let inspect = module.into_inspect();
+
+    let region = inspect.variables.get("region");
+    assert!(region.is_some());
+    let region = region.unwrap();
+    assert_eq!("string", region.type_);
+    assert!(region.required);
+
+    let web = inspect.managed_resources.get("aws_instance.web");
+    assert!(web.is_some());
+    let web = web.unwrap();
+    assert_eq!(tfconfig::InspectResourceMode::Managed, web.mode);
+    assert_eq!("west", web.provider.alias);
+
+    let ubuntu = inspect.data_resources.get("data.aws_ami.ubuntu");
+    assert!(ubuntu.is_some());
+    assert_eq!("aws", ubuntu.unwrap().provider.name);
+
+    Ok(())
+}
+
+#[test]
+fn test_required_providers_quoted_key() -> result::Result<(), Box<dyn Error>> {
+    let provider: hcl::Body = hcl::from_str(r#"aws = { source = "hashicorp/aws" }"#)?;
+    let provider = provider.attributes().next().unwrap().expr().clone();
+    let file = hcl::Body::builder()
+        .add_block(
+            hcl::Block::builder("terraform")
+                .add_block(
+                    hcl::Block::builder("required_providers")
+                        .add_attribute(hcl::Attribute::new(
+                            hcl::Identifier::unchecked("\"aws\""),
+                            provider,
+                        ))
+                        .build(),
+                )
+                .build(),
+        )
+        .build();
+
+    let mut module = Module::default();
+    tfconfig::load_module_from_file(Path::new("main.tf"), file, &mut module)?;
+
+    let aws = module.required_providers.get("aws");
+    assert!(aws.is_some());
+    assert_eq!("hashicorp/aws", aws.unwrap().source);
+    assert!(!module.required_providers.contains_key("\"aws\""));
+
+    Ok(())
+}
+
+#[test]
+fn test_resource_attributes() -> result::Result<(), Box<dyn Error>> {
+    let module = tfconfig::load_module_from_str(
+        Path::new("main.tf"),
+        r#"resource "aws_s3_bucket" "logs" {
+            count  = 2
+            bucket = "logs-${count.index}"
+            acl    = "private"
+
+            lifecycle {
+                prevent_destroy = true
+            }
+        }
+
+        data "aws_ami" "ubuntu" {
+            most_recent = true
+            depends_on  = [aws_s3_bucket.logs]
+        }"#,
+    )?;
+
+    let logs = module.managed_resources.get("aws_s3_bucket.logs");
+    assert!(logs.is_some());
+    let logs = logs.unwrap();
+    let mut keys: Vec<&String> = logs.attributes.keys().collect();
+    keys.sort();
+    assert_eq!(vec!["acl", "bucket"], keys);
+    assert_eq!(
+        Some(&hcl::Expression::String("private".to_string())),
+        logs.attributes.get("acl")
+    );
+
+    let ubuntu = module.data_resources.get("data.aws_ami.ubuntu");
+    assert!(ubuntu.is_some());
+    let ubuntu = ubuntu.unwrap();
+    assert_eq!(1, ubuntu.attributes.len());
+    assert_eq!(
+        Some(&hcl::Expression::Bool(true)),
+        ubuntu.attributes.get("most_recent")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_load_module_include_hidden() -> result::Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new("test_load_module_include_hidden")?;
+    let tmp_dir_path = tmp_dir.path();
+    fs::write(tmp_dir_path.join(".main.tf"), r#"variable "hidden" {}"#)?;
+    fs::write(tmp_dir_path.join("main.tf~"), r#"variable "backup" {}"#)?;
+    fs::write(tmp_dir_path.join("#main.tf#"), r#"variable "autosave" {}"#)?;
+    fs::write(tmp_dir_path.join("README.md"), "# Network module")?;
+
+    let module = tfconfig::load_module(tmp_dir_path, true)?;
+    assert!(module.variables.is_empty());
+
+    let options = tfconfig::LoadOptions::new()
+        .strict(true)
+        .include_hidden(true);
+    let module = tfconfig::load_module_with_options(tmp_dir_path, &options)?;
+    let mut names: Vec<&String> = module.variables.keys().collect();
+    names.sort();
+    assert_eq!(vec!["hidden"], names);
+
+    Ok(())
+}
+
+#[test]
+fn test_required_providers_merged_across_files() -> result::Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new("test_required_providers_merged_across_files")?;
+    let tmp_dir_path = tmp_dir.path();
+    for (file_name, version) in [
+        ("a.tf", ">= 4.0"),
+        ("b.tf", ">= 4.20"),
+        ("c.tf", "< 5.0"),
+        ("d.tf", ">= 4.0"),
+    ] {
+        fs::write(
+            tmp_dir_path.join(file_name),
+            format!(
+                r#"terraform {{
+                    required_providers {{
+                        aws = {{
+                            source  = "hashicorp/aws"
+                            version = "{version}"
+                        }}
+                    }}
+                }}"#
+            ),
+        )?;
+    }
+
+    let module = tfconfig::load_module(tmp_dir_path, true)?;
+    assert!(module.diagnostics.is_empty());
+
+    let aws = module.required_providers.get("aws");
+    assert!(aws.is_some());
+    let mut constraints = aws.unwrap().version_constraints.clone();
+    constraints.sort();
+    assert_eq!(vec!["< 5.0", ">= 4.0", ">= 4.20"], constraints);
+
+    fs::write(
+        tmp_dir_path.join("e.tf"),
+        r#"terraform {
+            required_providers {
+                aws = {
+                    source = "mycorp/aws"
+                }
+            }
+        }"#,
+    )?;
+
+    let module = tfconfig::load_module(tmp_dir_path, true)?;
+    assert_eq!(1, module.diagnostics.len());
+    assert_eq!(DiagnosticSeverity::Error, module.diagnostics[0].severity);
+
+    Ok(())
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn test_tracing_skipped_file_event() -> result::Result<(), Box<dyn Error>> {
+    use std::sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    };
+    use tracing::{
+        field::{Field, Visit},
+        span, Event, Metadata, Subscriber,
+    };
+
+    #[derive(Default)]
+    struct MessageVisitor(String);
+
+    impl Visit for MessageVisitor {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            if field.name() == "message" {
+                self.0 = format!("{value:?}");
+            }
+        }
+    }
+
+    #[derive(Default)]
+    struct CapturingSubscriber {
+        next_id: AtomicU64,
+        messages: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Subscriber for CapturingSubscriber {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+            span::Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+        }
+
+        fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+
+        fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let mut visitor = MessageVisitor::default();
+            event.record(&mut visitor);
+            self.messages.lock().unwrap().push(visitor.0);
+        }
+
+        fn enter(&self, _: &span::Id) {}
+
+        fn exit(&self, _: &span::Id) {}
+    }
+
+    let tmp_dir = TempDir::new("test_tracing_skipped_file_event")?;
+    let tmp_dir_path = tmp_dir.path();
+    fs::write(tmp_dir_path.join("main.tf"), "variable {")?;
+
+    let subscriber = CapturingSubscriber::default();
+    let messages = subscriber.messages.clone();
+    tracing::subscriber::with_default(subscriber, || tfconfig::load_module(tmp_dir_path, false))?;
+
+    let messages = messages.lock().unwrap();
+    assert!(messages
+        .iter()
+        .any(|message| message.starts_with("skipped file")));
+
+    Ok(())
+}
+
+#[test]
+fn test_load_module_from_bodies() -> result::Result<(), Box<dyn Error>> {
+    let primary: hcl::Body = hcl::from_str(
+        r#"variable "region" {
+            default = "us-east-1"
+        }
+
+        variable "name" {}"#,
+    )?;
+    let overrides: hcl::Body = hcl::from_str(
+        r#"variable "region" {
+            default = "eu-west-1"
+        }"#,
+    )?;
+
+    let bodies = vec![
+        (PathBuf::from("network/main_override.tf"), overrides),
+        (PathBuf::from("network/main.tf"), primary),
+    ];
+    let module = tfconfig::load_module_from_bodies(&bodies, true)?;
+
+    assert_eq!(Path::new("network"), module.path);
+    assert_eq!(2, module.variable_count());
+
+    let region = module.variables.get("region");
+    assert!(region.is_some());
+    assert_eq!(
+        Some(hcl::Expression::String("eu-west-1".to_string())),
+        region.unwrap().default
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_load_module_from_bodies_strict_labels() -> result::Result<(), Box<dyn Error>> {
+    let body: hcl::Body = hcl::from_str(r#"terraform "extra" { required_version = ">= 1.0" }"#)?;
+    let bodies = vec![(PathBuf::from("main.tf"), body)];
+
+    let res = tfconfig::load_module_from_bodies(&bodies, true);
+    assert!(matches!(res, Err(TfConfigError::UnexpectedLabels { .. })));
+
+    let module = tfconfig::load_module_from_bodies(&bodies, false)?;
+    assert!(module.required_core.is_empty());
+    assert_eq!(1, module.diagnostics.len());
+    assert_eq!(DiagnosticSeverity::Error, module.diagnostics[0].severity);
+
+    Ok(())
+}
+
+#[test]
+fn test_providers_missing_source() -> result::Result<(), Box<dyn Error>> {
+    let module = tfconfig::load_module_from_str(
+        Path::new("main.tf"),
+        r#"terraform {
+            required_providers {
+                aws = {
+                    source = "hashicorp/aws"
+                }
+            }
+        }
+
+        resource "google_compute_instance" "web" {}
+
+        data "terraform_remote_state" "network" {}"#,
+    )?;
+
+    assert_eq!(vec!["google"], module.providers_missing_source());
+
+    Ok(())
+}
+
+#[test]
+fn test_required_core_multiple_terraform_blocks() -> result::Result<(), Box<dyn Error>> {
+    let module = tfconfig::load_module_from_str(
+        Path::new("versions.tf"),
+        r#"terraform {
+            required_version = ">= 1.0"
+        }
+
+        terraform {
+            required_version = "< 2.0"
+        }"#,
+    )?;
+    assert_eq!(vec![">= 1.0", "< 2.0"], module.required_core);
+
+    let module = tfconfig::load_module_from_str(
+        Path::new("versions.tf"),
+        r#"terraform {
+            required_version = ">= 1.0"
+        }
+
+        terraform {
+            required_version = ">= 1.0"
+        }"#,
+    )?;
+    assert_eq!(vec![">= 1.0"], module.required_core);
+    assert_eq!(1, module.required_core_origins.len());
+
+    Ok(())
+}
+
+#[test]
+fn test_load_provider_requirements() -> result::Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new("test_load_provider_requirements")?;
+    let tmp_dir_path = tmp_dir.path();
+    fs::write(
+        tmp_dir_path.join("versions.tf"),
+        r#"terraform {
+            required_providers {
+                aws = {
+                    source  = "hashicorp/aws"
+                    version = ">= 4.0"
+                }
+            }
+        }"#,
+    )?;
+    fs::write(
+        tmp_dir_path.join("main.tf"),
+        r#"variable "region" {}
+
+        provider "google" {
+            version = "~> 5.0"
+        }
+
+        resource "aws_instance" "web" {}
+
+        data "azurerm_client_config" "current" {}"#,
+    )?;
+
+    let module = tfconfig::load_module(tmp_dir_path, true)?;
+    let providers = tfconfig::load_provider_requirements(tmp_dir_path, true)?;
+
+    let mut names: Vec<&String> = providers.keys().collect();
+    names.sort();
+    assert_eq!(vec!["aws", "azurerm", "google"], names);
+    for (name, req) in &providers {
+        let expected = module.required_providers.get(name);
+        assert!(expected.is_some());
+        let expected = expected.unwrap();
+        assert_eq!(expected.source, req.source);
+        assert_eq!(expected.version_constraints, req.version_constraints);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_load_provider_requirements_ignores_lock_file() -> result::Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new("test")?;
+    fs::write(
+        tmp_dir.path().join("main.tf"),
+        r#"resource "aws_instance" "web" {}"#,
+    )?;
+    fs::write(tmp_dir.path().join(".terraform.lock.hcl"), "provider {")?;
+
+    let options = tfconfig::LoadOptions::new()
+        .strict(true)
+        .load_lock_file(true);
+    assert!(tfconfig::load_module_with_options(tmp_dir.path(), &options).is_err());
+
+    let providers = tfconfig::load_provider_requirements(tmp_dir.path(), true)?;
+    assert!(providers.contains_key("aws"));
+
+    Ok(())
+}
+
+#[test]
+fn test_backend() -> result::Result<(), Box<dyn Error>> {
+    let module = tfconfig::load_module_from_str(
+        Path::new("main.tf"),
+        r#"terraform {
+            backend "s3" {
+                bucket = "terraform-state"
+                key    = "prod/terraform.tfstate"
+            }
+        }"#,
+    )?;
+
+    assert!(module.backend.is_some());
+    let backend = module.backend.unwrap();
+    assert_eq!("s3", backend.type_);
+    assert_eq!(2, backend.config.len());
+    assert!(!backend.is_partial());
+    assert!(module.terraform_nested_blocks.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_backend_partial() -> result::Result<(), Box<dyn Error>> {
+    let module = tfconfig::load_module_from_str(
+        Path::new("main.tf"),
+        r#"terraform {
+            backend "s3" {}
+        }"#,
+    )?;
+
+    assert!(module.backend.is_some());
+    let backend = module.backend.unwrap();
+    assert_eq!("s3", backend.type_);
+    assert!(backend.config.is_empty());
+    assert!(backend.is_partial());
+
+    Ok(())
+}
+
+#[test]
+fn test_effective_terraform_version_requirement() -> result::Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new("test_effective_terraform_version_requirement")?;
+    let tmp_dir_path = tmp_dir.path();
+    fs::write(
+        tmp_dir_path.join("main.tf"),
+        r#"terraform { required_version = ">= 1.3.0" }"#,
+    )?;
+    fs::write(
+        tmp_dir_path.join("versions.tf"),
+        r#"terraform { required_version = ">= 1.3.0, < 2.0.0" }"#,
+    )?;
+
+    let module = tfconfig::load_module(tmp_dir_path, true)?;
+    assert_eq!(
+        Some(">= 1.3.0, < 2.0.0".to_string()),
+        module.effective_terraform_version_requirement()
+    );
+
+    let module = tfconfig::load_module_from_str(Path::new("main.tf"), "")?;
+    assert_eq!(None, module.effective_terraform_version_requirement());
+
+    Ok(())
+}
+
+#[test]
+fn test_data_resource_provider() -> result::Result<(), Box<dyn Error>> {
+    let module = tfconfig::load_module_from_str(
+        Path::new("main.tf"),
+        r#"data "aws_ami" "east" {
+            provider = aws.east
+        }
+
+        data "google_client_config" "current" {
+            provider = mygoogle
+        }
+
+        data "aws_region" "current" {}"#,
+    )?;
+
+    let data = module.data_resources.get("data.aws_ami.east");
+    assert!(data.is_some());
+    assert_eq!(
+        Some(tfconfig::ProviderRef::new(
+            "aws".to_string(),
+            "east".to_string()
+        )),
+        data.unwrap().provider
+    );
+
+    let data = module
+        .data_resources
+        .get("data.google_client_config.current");
+    assert!(data.is_some());
+    assert_eq!("mygoogle", data.unwrap().provider.as_ref().unwrap().name);
+    assert!(module.required_providers.contains_key("mygoogle"));
+    assert!(!module.required_providers.contains_key("google"));
+
+    let data = module.data_resources.get("data.aws_region.current");
+    assert!(data.is_some());
+    assert_eq!(None, data.unwrap().provider);
+
+    Ok(())
+}
+
+#[test]
+fn test_module_clone() -> result::Result<(), Box<dyn Error>> {
+    let module = tfconfig::load_module_from_str(
+        Path::new("main.tf"),
+        r#"terraform {
+            required_providers {
+                aws = {
+                    source  = "hashicorp/aws"
+                    version = ">= 4.0"
+                }
+            }
+        }"#,
+    )?;
+
+    let mut snapshot = module.clone();
+    snapshot
+        .required_providers
+        .get_mut("aws")
+        .unwrap()
+        .version_constraints
+        .push("< 6.0".to_string());
+    snapshot.required_providers.remove("aws");
+
+    assert!(snapshot.required_providers.is_empty());
+    let aws = module.required_providers.get("aws");
+    assert!(aws.is_some());
+    assert_eq!(vec![">= 4.0"], aws.unwrap().version_constraints);
+
+    Ok(())
+}
+
+#[test]
+fn test_required_providers_unexpected_values() -> result::Result<(), Box<dyn Error>> {
+    let res = tfconfig::load_module_from_str(
+        Path::new("main.tf"),
+        r#"terraform {
+            required_providers {
+                aws = jsonencode({})
+            }
+        }"#,
+    );
+    assert!(matches!(
+        res,
+        Err(TfConfigError::UnexpectedExpr {
+            attribute_key,
+            expr: hcl::Expression::FuncCall(_),
+            file_name,
+        }) if attribute_key == "aws" && file_name == Path::new("main.tf")
+    ));
+
+    let res = tfconfig::load_module_from_str(
+        Path::new("main.tf"),
+        r#"terraform {
+            required_providers {
+                aws {
+                    source = "hashicorp/aws"
+                }
+            }
+        }"#,
+    );
+    assert!(matches!(
+        res,
+        Err(TfConfigError::UnexpectedBlock {
+            block_type,
+            parent,
+            file_name,
+        }) if block_type == "aws" && parent == "required_providers" && file_name == Path::new("main.tf")
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_required_providers_legacy_version_string() -> result::Result<(), Box<dyn Error>> {
+    let module = tfconfig::load_module_from_str(
+        Path::new("main.tf"),
+        r#"terraform {
+            required_providers {
+                aws = "~> 2.0, != 2.1.0"
+            }
+        }"#,
+    )?;
+
+    let aws = module.required_providers.get("aws");
+    assert!(aws.is_some());
+    let aws = aws.unwrap();
+    assert_eq!("", aws.source);
+    assert_eq!(vec!["~> 2.0", "!= 2.1.0"], aws.version_constraints);
+
+    Ok(())
+}
+
+#[test]
+fn test_load_module_on_read_error() -> result::Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new("test_load_module_on_read_error")?;
+    let tmp_dir_path = tmp_dir.path();
+    fs::write(tmp_dir_path.join("main.tf"), r#"variable "region" {}"#)?;
+    fs::write(tmp_dir_path.join("latin1.tf"), b"# caf\xe9\n")?;
+
+    let options = tfconfig::LoadOptions::new()
+        .strict(true)
+        .on_read_error(tfconfig::ReadErrorPolicy::Diagnose);
+    let module = tfconfig::load_module_with_options(tmp_dir_path, &options)?;
+    assert!(module.variables.contains_key("region"));
+    assert_eq!(1, module.diagnostics.len());
+    assert_eq!(DiagnosticSeverity::Error, module.diagnostics[0].severity);
+    assert_eq!("Failed to read file", module.diagnostics[0].summary);
+
+    let options = tfconfig::LoadOptions::new()
+        .strict(true)
+        .on_read_error(tfconfig::ReadErrorPolicy::Skip);
+    let module = tfconfig::load_module_with_options(tmp_dir_path, &options)?;
+    assert!(module.diagnostics.is_empty());
+
+    let options = tfconfig::LoadOptions::new().on_read_error(tfconfig::ReadErrorPolicy::Fail);
+    let res = tfconfig::load_module_with_options(tmp_dir_path, &options);
+    assert!(matches!(res, Err(TfConfigError::Io(_))));
+
+    assert!(matches!(
+        tfconfig::load_module(tmp_dir_path, true),
+        Err(TfConfigError::Io(_))
+    ));
+    assert!(tfconfig::load_module(tmp_dir_path, false)?
+        .diagnostics
+        .is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_module_display() -> result::Result<(), Box<dyn Error>> {
+    let module = tfconfig::load_module_from_str(
+        Path::new("config/main.tf"),
+        r#"terraform {
+            required_version = ">= 1.3.0, < 2.0.0"
+
+            required_providers {
+                aws = {
+                    source  = "hashicorp/aws"
+                    version = "~> 5.0"
+                }
+            }
+        }
+
+        variable "region" {}
+
+        resource "aws_instance" "web" {}"#,
+    )?;
+
+    let display = module.to_string();
+    assert!(display.starts_with("Module config\n"));
+    assert!(display.contains("Terraform: 1 constraint(s) (>= 1.3.0, < 2.0.0)"));
+    assert!(display.contains("aws: registry.terraform.io/hashicorp/aws (~> 5.0)"));
+    assert!(display.contains("Variables: 1"));
+    assert!(display.contains("Managed resources: 1"));
+
+    Ok(())
+}
+
+#[test]
+fn test_multiple_required_providers_blocks() -> result::Result<(), Box<dyn Error>> {
+    let module = tfconfig::load_module_from_str(
+        Path::new("main.tf"),
+        r#"terraform {
+            required_providers {
+                aws = {
+                    source  = "hashicorp/aws"
+                    version = ">= 4.0"
+                }
+            }
+        }
+
+        terraform {
+            required_providers {
+                aws = {
+                    version = "< 6.0"
+                }
+                google = {
+                    source = "hashicorp/google"
+                }
+            }
+        }"#,
+    )?;
+
+    assert!(module.diagnostics.is_empty());
+    assert_eq!(2, module.required_providers.len());
+    let aws = module.required_providers.get("aws");
+    assert!(aws.is_some());
+    let aws = aws.unwrap();
+    assert_eq!("hashicorp/aws", aws.source);
+    assert_eq!(vec![">= 4.0", "< 6.0"], aws.version_constraints);
+    assert!(module.required_providers.contains_key("google"));
+
+    let module = tfconfig::load_module_from_str(
+        Path::new("main.tf"),
+        r#"terraform {
+            required_providers {
+                aws = {
+                    source = "hashicorp/aws"
+                }
+            }
+
+            required_providers {
+                google = {
+                    source = "hashicorp/google"
+                }
+            }
+        }"#,
+    )?;
+
+    assert_eq!(2, module.required_providers.len());
+    assert_eq!(1, module.diagnostics.len());
+    assert_eq!(DiagnosticSeverity::Error, module.diagnostics[0].severity);
+    assert_eq!(
+        "Duplicate required providers configuration",
+        module.diagnostics[0].summary
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_load_module_canonicalize_path() -> result::Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new_in("target", "test_load_module_canonicalize_path")?;
+    let tmp_dir_path = tmp_dir.path().strip_prefix(std::env::current_dir()?)?;
+    assert!(tmp_dir_path.is_relative());
+    fs::write(tmp_dir_path.join("main.tf"), r#"variable "region" {}"#)?;
+
+    let module = tfconfig::load_module(tmp_dir_path, true)?;
+    assert_eq!(tmp_dir_path, module.path);
+
+    let options = tfconfig::LoadOptions::new().canonicalize_path(true);
+    let module = tfconfig::load_module_with_options(tmp_dir_path, &options)?;
+    assert!(module.path.is_absolute());
+    assert_eq!(fs::canonicalize(tmp_dir_path)?, module.path);
+    assert!(module.variables.contains_key("region"));
+
+    Ok(())
+}
+
+#[test]
+fn test_load_module_lock_file() -> result::Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new("test_load_module_lock_file")?;
+    let tmp_dir_path = tmp_dir.path();
+    fs::write(
+        tmp_dir_path.join("main.tf"),
+        r#"terraform {
+            required_providers {
+                aws = {
+                    source  = "hashicorp/aws"
+                    version = ">= 4.0"
+                }
+            }
+        }"#,
+    )?;
+
+    let options = tfconfig::LoadOptions::new()
+        .strict(true)
+        .load_lock_file(true);
+    let module = tfconfig::load_module_with_options(tmp_dir_path, &options)?;
+    assert!(module.lock_file.is_none());
+
+    fs::write(
+        tmp_dir_path.join(tfconfig::LOCK_FILE_NAME),
+        r#"# This file is maintained automatically by "terraform init".
+        # Manual edits may be lost in future updates.
+
+        provider "registry.terraform.io/hashicorp/aws" {
+          version     = "5.31.0"
+          constraints = ">= 4.0"
+          hashes = [
+            "h1:ltxyuBWIy9cq0kIKDJH1jeWJy/y7XJLjS4QrsQK4plA=",
+            "zh:0cdb9c2083bf0902442384f7309367791e4640581652dda456f2d6d7abf0de8d",
+          ]
+        }"#,
+    )?;
+
+    let module = tfconfig::load_module(tmp_dir_path, true)?;
+    assert!(module.lock_file.is_none());
+
+    let module = tfconfig::load_module_with_options(tmp_dir_path, &options)?;
+    assert!(module.lock_file.is_some());
+    let lock_file = module.lock_file.unwrap();
+    let aws = lock_file.provider("hashicorp/aws");
+    assert!(aws.is_some());
+    let aws = aws.unwrap();
+    assert_eq!("5.31.0", aws.version);
+    assert_eq!(vec![">= 4.0"], aws.constraints);
+    assert_eq!(2, aws.hashes.len());
+    assert!(lock_file
+        .provider("registry.terraform.io/hashicorp/aws")
+        .is_some());
+    assert!(lock_file.provider("hashicorp/google").is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_load_module_invalid_lock_file() -> result::Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new("test_load_module_invalid_lock_file")?;
+    let tmp_dir_path = tmp_dir.path();
+    fs::write(
+        tmp_dir_path.join("main.tf"),
+        r#"resource "aws_instance" "web" {}"#,
+    )?;
+    let options = tfconfig::LoadOptions::new().load_lock_file(true);
+
+    fs::write(tmp_dir_path.join(tfconfig::LOCK_FILE_NAME), "provider {")?;
+    let module = tfconfig::load_module_with_options(tmp_dir_path, &options)?;
+    assert!(module.lock_file.is_none());
+    assert!(module.managed_resources.contains_key("aws_instance.web"));
+    assert_eq!(1, module.diagnostics.len());
+    assert_eq!("Failed to parse file", module.diagnostics[0].summary);
+
+    fs::write(
+        tmp_dir_path.join(tfconfig::LOCK_FILE_NAME),
+        r#"provider {
+          version = "5.31.0"
+        }
+
+        provider "registry.terraform.io/hashicorp/aws" {
+          version = "5.31.0"
+        }"#,
+    )?;
+    let module = tfconfig::load_module_with_options(tmp_dir_path, &options)?;
+    assert_eq!(1, module.diagnostics.len());
+    assert_eq!("Invalid lock file entry", module.diagnostics[0].summary);
+    let lock_file = module.lock_file.unwrap();
+    assert_eq!(1, lock_file.providers.len());
+    assert!(lock_file.provider("hashicorp/aws").is_some());
+
+    assert!(matches!(
+        tfconfig::load_module_with_options(tmp_dir_path, &options.clone().strict(true)),
+        Err(TfConfigError::UnexpectedLabels { .. })
+    ));
+
+    let options = options.max_file_size(64);
+    let module = tfconfig::load_module_with_options(tmp_dir_path, &options)?;
+    assert!(module.lock_file.is_none());
+    assert_eq!(1, module.diagnostics.len());
+    assert_eq!("File too large", module.diagnostics[0].summary);
+
+    assert!(matches!(
+        tfconfig::load_module_with_options(tmp_dir_path, &options.strict(true)),
+        Err(TfConfigError::FileTooLarge { .. })
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_resource_addresses() -> result::Result<(), Box<dyn Error>> {
+    let module = tfconfig::load_module_from_str(
+        Path::new("main.tf"),
+        r#"resource "aws_instance" "web" {}
+
+        data "aws_ami" "ubuntu" {}"#,
+    )?;
+
+    assert_eq!(
+        "aws_instance.web",
+        module.managed_resources["aws_instance.web"].address()
+    );
+    assert_eq!(
+        "data.aws_ami.ubuntu",
+        module.data_resources["data.aws_ami.ubuntu"].address()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_load_module_does_not_retain_contents() -> result::Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new("test_load_module_does_not_retain_contents")?;
+    let tmp_dir_path = tmp_dir.path();
+    let marker = "generated-padding-marker";
+    for i in 0..3 {
+        let padding = format!("# {marker}\n").repeat(10_000);
+        fs::write(
+            tmp_dir_path.join(format!("generated{i}.tf")),
+            format!("{padding}variable \"var{i}\" {{}}\n"),
+        )?;
+    }
+
+    let module = tfconfig::load_module(tmp_dir_path, true)?;
+    assert_eq!(3, module.variables.len());
+    assert!(!format!("{module:?}").contains(marker));
+
+    Ok(())
+}
+
+#[test]
+fn test_load_module_dev_overrides() -> result::Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new("test_load_module_dev_overrides")?;
+    let tmp_dir_path = tmp_dir.path();
+    fs::write(
+        tmp_dir_path.join("main.tf"),
+        r#"terraform {
+            required_providers {
+                aws = {
+                    source = "hashicorp/aws"
+                }
+                google = {
+                    source = "hashicorp/google"
+                }
+            }
+        }"#,
+    )?;
+
+    let dev_overrides = HashMap::from([(
+        "hashicorp/aws".to_string(),
+        PathBuf::from("/home/dev/terraform-provider-aws"),
+    )]);
+    let options = tfconfig::LoadOptions::new().dev_overrides(dev_overrides);
+    let module = tfconfig::load_module_with_options(tmp_dir_path, &options)?;
+
+    assert_eq!(
+        Some(PathBuf::from("/home/dev/terraform-provider-aws")),
+        module.required_providers["aws"].dev_override
+    );
+    assert_eq!(None, module.required_providers["google"].dev_override);
+
+    Ok(())
+}
+
+#[test]
+fn test_parse_str() -> result::Result<(), Box<dyn Error>> {
+    let module = tfconfig::parse_str(r#"variable "region" {}"#)?;
+    assert_eq!(PathBuf::new(), module.path);
+    assert!(module.variables.contains_key("region"));
+    assert_eq!(vec![PathBuf::new()], module.touched_files());
+
+    Ok(())
+}
+
+#[test]
+fn test_load_module_leading_line() -> result::Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new("test_load_module_leading_line")?;
+    let tmp_dir_path = tmp_dir.path();
+    fs::write(
+        tmp_dir_path.join("shebang.tf"),
+        "#!/usr/bin/env terraform\nvariable \"region\" {}\n",
+    )?;
+    let generated_file_path = tmp_dir_path.join("generated.tf");
+    fs::write(
+        &generated_file_path,
+        "\n\nGenerated by tool v1\nvariable \"zone\" {}\n",
+    )?;
+
+    let module = tfconfig::load_module(tmp_dir_path, false)?;
+    assert!(module.variables.contains_key("region"));
+    assert!(!module.variables.contains_key("zone"));
+    assert_eq!(1, module.diagnostics.len());
+    let diagnostic = &module.diagnostics[0];
+    assert_eq!("Failed to parse file", diagnostic.summary);
+    assert!(diagnostic.detail.contains("Generated by tool v1"));
+    assert_eq!(
+        Some(tfconfig::SourcePos::new(generated_file_path, 3)),
+        diagnostic.pos
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_required_providers_sorted() -> result::Result<(), Box<dyn Error>> {
+    let module = tfconfig::load_module_from_str(
+        Path::new("main.tf"),
+        r#"terraform {
+            required_providers {
+                random = {
+                    source = "hashicorp/random"
+                }
+                aws = {
+                    source = "hashicorp/aws"
+                }
+                google = {
+                    source = "hashicorp/google"
+                }
+            }
+        }"#,
+    )?;
+
+    let providers = module.required_providers_sorted();
+    let names: Vec<&str> = providers.iter().map(|(name, _)| *name).collect();
+    assert_eq!(vec!["aws", "google", "random"], names);
+    assert_eq!("hashicorp/aws", providers[0].1.source);
+
+    Ok(())
+}
+
+#[test]
+fn test_output_referenced_addresses() -> result::Result<(), Box<dyn Error>> {
+    let module = tfconfig::load_module_from_str(
+        Path::new("main.tf"),
+        r#"output "ip" {
+            value = aws_instance.web.public_ip
+        }
+
+        output "summary" {
+            value = {
+                name  = "${var.prefix}-${local.suffix}"
+                ids   = [for instance in aws_instance.workers : instance.id]
+                first = aws_instance.workers[0].id
+                zone  = var.zone != null ? var.zone : data.aws_availability_zones.all.names[0]
+                tags  = merge(var.tags, { Name = var.prefix })
+            }
+        }
+
+        output "literal" {
+            value = "static"
+        }"#,
+    )?;
+
+    assert_eq!(
+        vec!["aws_instance.web.public_ip"],
+        module.outputs["ip"].referenced_addresses()
+    );
+    assert_eq!(
+        vec![
+            "var.prefix",
+            "local.suffix",
+            "aws_instance.workers",
+            "var.zone",
+            "data.aws_availability_zones.all.names",
+            "var.tags",
+        ],
+        module.outputs["summary"].referenced_addresses()
+    );
+    assert!(module.outputs["literal"].referenced_addresses().is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_load_module_max_file_size() -> result::Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new("test_load_module_max_file_size")?;
+    let tmp_dir_path = tmp_dir.path();
+    fs::write(tmp_dir_path.join("small.tf"), r#"variable "region" {}"#)?;
+    let large_file_path = tmp_dir_path.join("large.tf");
+    fs::write(
+        &large_file_path,
+        format!("{}variable \"zone\" {{}}", "# padding\n".repeat(100)),
+    )?;
+
+    let options = tfconfig::LoadOptions::new().max_file_size(100);
+    let module = tfconfig::load_module_with_options(tmp_dir_path, &options)?;
+    assert!(module.variables.contains_key("region"));
+    assert!(!module.variables.contains_key("zone"));
+    assert_eq!(1, module.diagnostics.len());
+    assert_eq!("File too large", module.diagnostics[0].summary);
+
+    let options = options.strict(true);
+    let res = tfconfig::load_module_with_options(tmp_dir_path, &options);
+    assert!(matches!(
+        res,
+        Err(TfConfigError::FileTooLarge { path, size, limit })
+            if path == large_file_path && size > 100 && limit == 100
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_provider_config_nested_blocks() -> result::Result<(), Box<dyn Error>> {
+    let module = tfconfig::load_module_from_str(
+        Path::new("main.tf"),
+        r#"provider "aws" {
+            region = "us-east-1"
+
+            assume_role {
+                role_arn     = "arn:aws:iam::123456789012:role/deploy"
+                session_name = "terraform"
+            }
+
+            endpoints {
+                s3 = "http://localhost:4566"
+            }
+        }"#,
+    )?;
+
+    let config = module.provider_configs.get("aws");
+    assert!(config.is_some());
+    let nested_blocks = &config.unwrap().nested_blocks;
+    assert_eq!(2, nested_blocks.len());
+    let (block_type, body) = &nested_blocks[0];
+    assert_eq!("assume_role", block_type);
+    assert!(body.attributes().any(|attr| attr.key() == "role_arn"));
+    assert_eq!("endpoints", nested_blocks[1].0);
+
+    Ok(())
+}
+
+#[test]
+fn test_error_other_source_chain() -> result::Result<(), Box<dyn Error>> {
+    #[derive(Debug)]
+    struct Wrapper(std::io::Error);
+
+    impl std::fmt::Display for Wrapper {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "wrapper failed")
+        }
+    }
+
+    impl Error for Wrapper {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    let root = std::io::Error::other("root cause");
+    let boxed: Box<dyn Error + Send + Sync> = Box::new(Wrapper(root));
+    let err = TfConfigError::from(boxed);
+    assert_eq!("wrapper failed", err.to_string());
+
+    let mut chain = vec![];
+    let mut source = err.source();
+    while let Some(e) = source {
+        chain.push(e.to_string());
+        source = e.source();
+    }
+    assert_eq!(vec!["wrapper failed", "root cause"], chain);
+
+    Ok(())
+}
+
+#[cfg(feature = "archive")]
+#[test]
+fn test_error_other_from_invalid_archive() {
+    let res = tfconfig::load_module_from_archive(
+        std::io::Cursor::new(b"not a zip archive".to_vec()),
+        tfconfig::ArchiveFormat::Zip,
+        true,
+    );
+
+    assert!(matches!(res, Err(TfConfigError::Other(_))));
+    let err = res.unwrap_err();
+    assert!(!err.is_recoverable());
+    let source = err.source();
+    assert!(source.is_some());
+    let source = source.unwrap();
+    assert!(source.is::<zip::result::ZipError>());
+    assert_eq!(err.to_string(), source.to_string());
+}
+
+#[test]
+fn test_load_module_max_diagnostics() -> result::Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new("test_load_module_max_diagnostics")?;
+    let tmp_dir_path = tmp_dir.path();
+    for i in 0..5 {
+        fs::write(tmp_dir_path.join(format!("broken{i}.tf")), "variable {")?;
+    }
+
+    let options = tfconfig::LoadOptions::new().max_diagnostics(3);
+    let module = tfconfig::load_module_with_options(tmp_dir_path, &options)?;
+    assert_eq!(4, module.diagnostics.len());
+    assert!(module.diagnostics[..3]
+        .iter()
+        .all(|diag| diag.summary == "Failed to parse file"));
+    let marker = &module.diagnostics[3];
+    assert_eq!(DiagnosticSeverity::Warning, marker.severity);
+    assert_eq!("Diagnostics suppressed", marker.summary);
+    assert!(marker.detail.starts_with("2 more"));
+
+    let options = tfconfig::LoadOptions::new().max_diagnostics(5);
+    let module = tfconfig::load_module_with_options(tmp_dir_path, &options)?;
+    assert_eq!(5, module.diagnostics.len());
+
+    Ok(())
+}
+
+#[test]
+fn test_resources_using_provider() -> result::Result<(), Box<dyn Error>> {
+    let module = tfconfig::load_module_from_str(
+        Path::new("main.tf"),
+        r#"resource "aws_instance" "web" {}
+
+        resource "aws_s3_bucket" "logs" {}
+
+        resource "google_compute_instance" "vm" {}
+
+        resource "google_compute_instance" "beta" {
+            provider = google-beta
+        }
+
+        data "aws_ami" "ubuntu" {}
+
+        data "google_client_config" "current" {
+            provider = aws.east
+        }"#,
+    )?;
+
+    let addresses: Vec<String> = module
+        .resources_using_provider("aws")
+        .iter()
+        .map(|resource| resource.address())
+        .collect();
+    assert_eq!(vec!["aws_instance.web", "aws_s3_bucket.logs"], addresses);
+
+    let addresses: Vec<String> = module
+        .resources_using_provider("google")
+        .iter()
+        .map(|resource| resource.address())
+        .collect();
+    assert_eq!(vec!["google_compute_instance.vm"], addresses);
+
+    let addresses: Vec<String> = module
+        .data_resources_using_provider("aws")
+        .iter()
+        .map(|resource| resource.address())
+        .collect();
+    assert_eq!(
+        vec!["data.aws_ami.ubuntu", "data.google_client_config.current"],
+        addresses
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_misplaced_terraform_settings() -> result::Result<(), Box<dyn Error>> {
+    let module = tfconfig::load_module_from_str(
+        Path::new("main.tf"),
+        r#"resource "null_resource" "example" {
+            required_providers {
+                aws = {
+                    source = "hashicorp/aws"
+                }
+            }
+        }
+
+        required_version = ">= 1.0""#,
+    )?;
+
+    assert!(!module.required_providers.contains_key("aws"));
+    assert!(module.required_core.is_empty());
+    assert_eq!(2, module.diagnostics.len());
+    assert!(module.diagnostics.iter().all(|diag| {
+        diag.severity == DiagnosticSeverity::Warning
+            && diag.summary == "Misplaced Terraform setting"
+    }));
+    assert!(module.diagnostics[0]
+        .detail
+        .contains(r#""resource null_resource example""#));
+    assert!(module.diagnostics[1].detail.contains("required_version"));
+
+    Ok(())
+}
+
+#[test]
+fn test_terraform_setting_names_in_locals_and_module_inputs() -> result::Result<(), Box<dyn Error>>
+{
+    let module = tfconfig::load_module_from_str(
+        Path::new("main.tf"),
+        r#"locals {
+            required_version   = ">= 1.0"
+            required_providers = ["aws"]
+        }
+
+        module "network" {
+            source           = "./network"
+            required_version = local.required_version
+        }"#,
+    )?;
+
+    assert!(module.diagnostics.is_empty());
+    assert!(module.required_core.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_source_addr_is_valid_host() -> result::Result<(), Box<dyn Error>> {
+    let module = tfconfig::load_module_from_str(
+        Path::new("main.tf"),
+        r#"terraform {
+            required_providers {
+                aws = {
+                    source = "hashicorp/aws"
+                }
+                internal = {
+                    source = "terraform.example.com:8443/myorg/internal"
+                }
+                bogus = {
+                    source = "not a host/ns/type"
+                }
+            }
+        }"#,
+    )?;
+
+    let host_is_valid = |name: &str| {
+        module.required_providers[name]
+            .source_parts()
+            .map(|addr| addr.is_valid_host())
+    };
+    assert_eq!(Some(true), host_is_valid("aws"));
+    assert_eq!(Some(true), host_is_valid("internal"));
+    assert_eq!(Some(false), host_is_valid("bogus"));
+
+    for hostname in [
+        "-example.com",
+        "example..com",
+        "example.com:",
+        "example.com:http",
+    ] {
+        let addr =
+            tfconfig::SourceAddr::new(hostname.to_string(), "ns".to_string(), "type".to_string());
+        assert!(!addr.is_valid_host(), "{hostname}");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_module_append_from_str() -> result::Result<(), Box<dyn Error>> {
+    let mut module = Module::new(PathBuf::from("config"));
+    module.append_from_str(
+        r#"terraform {
+            required_providers {
+                aws = {
+                    source  = "hashicorp/aws"
+                    version = ">= 4.0"
+                }
+            }
+        }
+
+        variable "region" {}"#,
+        Path::new("config/main.tf"),
+    )?;
+    module.append_from_str(
+        r#"terraform {
+            required_providers {
+                aws = {
+                    source  = "mycorp/aws"
+                    version = "~> 5.0"
+                }
+            }
+        }"#,
+        Path::new("config/main_override.tf"),
+    )?;
+
+    let aws = module.required_providers.get("aws");
+    assert!(aws.is_some());
+    let aws = aws.unwrap();
+    assert_eq!("mycorp/aws", aws.source);
+    assert_eq!(vec!["~> 5.0"], aws.version_constraints);
+    assert!(module.variables.contains_key("region"));
+    assert_eq!(
+        vec![
+            PathBuf::from("config/main.tf"),
+            PathBuf::from("config/main_override.tf")
+        ],
+        module.touched_files()
+    );
+
+    let res = module.append_from_str("variable {", Path::new("config/broken.tf"));
+    assert!(matches!(res, Err(TfConfigError::Parse(_))));
+
+    Ok(())
+}
+
+#[test]
+fn test_variable_var_type() -> result::Result<(), Box<dyn Error>> {
+    use tfconfig::VarType;
+
+    let module = tfconfig::load_module_from_str(
+        Path::new("main.tf"),
+        r#"variable "settings" {
+            type    = any
+            default = { a = 1, b = [2, 3] }
+        }
+
+        variable "servers" {
+            type = list(object({
+                name = string
+                tags = optional(map(string), {})
+            }))
+        }
+
+        variable "legacy" {
+            type = "map"
+        }
+
+        variable "untyped" {}"#,
+    )?;
+
+    let settings = &module.variables["settings"];
+    assert_eq!(Some(VarType::Any), settings.var_type());
+    let default: hcl::Object<hcl::ObjectKey, hcl::Expression> = [
+        (
+            hcl::ObjectKey::Identifier("a".into()),
+            hcl::Expression::from(1),
+        ),
+        (
+            hcl::ObjectKey::Identifier("b".into()),
+            hcl::Expression::Array(vec![hcl::Expression::from(2), hcl::Expression::from(3)]),
+        ),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(Some(hcl::Expression::Object(default)), settings.default);
+
+    assert_eq!(
+        Some(VarType::List(Box::new(VarType::Object(
+            [
+                ("name".to_string(), VarType::String),
+                (
+                    "tags".to_string(),
+                    VarType::Optional(Box::new(VarType::Map(Box::new(VarType::String))))
+                ),
+            ]
+            .into()
+        )))),
+        module.variables["servers"].var_type()
+    );
+    assert_eq!(
+        Some(VarType::Map(Box::new(VarType::Any))),
+        module.variables["legacy"].var_type()
+    );
+    assert_eq!(None, module.variables["untyped"].var_type());
+
+    Ok(())
+}
+
+#[test]
+fn test_provider_requirement_for_source() -> result::Result<(), Box<dyn Error>> {
+    let module = tfconfig::load_module_from_str(
+        Path::new("main.tf"),
+        r#"terraform {
+            required_providers {
+                rand = {
+                    source  = "HashiCorp/random"
+                    version = "~> 3.0"
+                }
+                aws = {
+                    version = ">= 5.0"
+                }
+            }
+        }"#,
+    )?;
+
+    let random = module.provider_requirement_for_source("hashicorp/random");
+    assert!(random.is_some());
+    assert_eq!(vec!["~> 3.0"], random.unwrap().version_constraints);
+    assert!(module
+        .provider_requirement_for_source("registry.terraform.io/hashicorp/random")
+        .is_some());
+
+    let aws = module.provider_requirement_for_source("hashicorp/aws");
+    assert!(aws.is_some());
+    assert_eq!(vec![">= 5.0"], aws.unwrap().version_constraints);
+
+    assert!(module
+        .provider_requirement_for_source("hashicorp/google")
+        .is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_terraform_block_labels() -> result::Result<(), Box<dyn Error>> {
+    let contents = r#"terraform "oops" {
+        required_version = ">= 1.0"
+    }"#;
+
+    let tmp_dir = TempDir::new("test_terraform_block_labels")?;
+    let tmp_dir_path = tmp_dir.path();
+    let file_path = tmp_dir_path.join("main.tf");
+    fs::write(&file_path, contents)?;
+
+    let module = tfconfig::load_module(tmp_dir_path, false)?;
+    assert!(module.required_core.is_empty());
+    assert_eq!(1, module.diagnostics.len());
+    assert_eq!(DiagnosticSeverity::Error, module.diagnostics[0].severity);
+    assert_eq!(
+        "Unexpected terraform block labels",
+        module.diagnostics[0].summary
+    );
+
+    let res = tfconfig::load_module(tmp_dir_path, true);
+    assert!(matches!(
+        res,
+        Err(TfConfigError::UnexpectedLabels {
+            block_type,
+            labels,
+            file_name,
+        }) if block_type == "terraform" && labels == vec!["oops"] && file_name == file_path
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_outputs_leaking_sensitive() -> result::Result<(), Box<dyn Error>> {
+    let module = tfconfig::load_module_from_str(
+        Path::new("main.tf"),
+        r#"variable "password" {
+            sensitive = true
+        }
+
+        variable "username" {}
+
+        output "connection_string" {
+            value = "postgres://${var.username}:${var.password}@db"
+        }
+
+        output "password" {
+            value     = var.password
+            sensitive = true
+        }
+
+        output "username" {
+            value = var.username
+        }"#,
+    )?;
+
+    assert_eq!(
+        vec!["connection_string"],
+        module.outputs_leaking_sensitive()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_encryption() -> result::Result<(), Box<dyn Error>> {
+    let module = tfconfig::load_module_from_str(
+        Path::new("main.tf"),
+        r#"terraform {
+            encryption {
+                key_provider "pbkdf2" "passphrase" {
+                    passphrase = var.passphrase
+                }
+
+                method "aes_gcm" "default" {
+                    keys = key_provider.pbkdf2.passphrase
+                }
+
+                state {
+                    method = method.aes_gcm.default
+                }
+            }
+        }"#,
+    )?;
+
+    assert!(module.encryption.is_some());
+    let encryption = module.encryption.unwrap();
+    assert_eq!(1, encryption.key_providers.len());
+    let key_provider = &encryption.key_providers[0];
+    assert_eq!("pbkdf2", key_provider.type_);
+    assert_eq!("passphrase", key_provider.name);
+    assert!(key_provider.attributes.contains_key("passphrase"));
+    assert_eq!(1, encryption.methods.len());
+    assert_eq!("aes_gcm", encryption.methods[0].type_);
+    assert_eq!(1, encryption.nested_blocks.len());
+    assert_eq!("state", encryption.nested_blocks[0].identifier());
+    assert!(module.terraform_nested_blocks.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_provider_local_name_collisions() -> result::Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new("test")?;
+    fs::write(
+        tmp_dir.path().join("a.tf"),
+        r#"terraform {
+            required_providers {
+                aws = { source = "hashicorp/aws" }
+                random = { source = "hashicorp/random" }
+            }
+        }"#,
+    )?;
+    fs::write(
+        tmp_dir.path().join("b.tf"),
+        r#"terraform {
+            required_providers {
+                aws = { source = "example/aws" }
+                random = { source = "registry.terraform.io/hashicorp/random" }
+            }
+        }"#,
+    )?;
+
+    let module = tfconfig::load_module(tmp_dir.path(), false)?;
+
+    assert_eq!(
+        vec![(
+            "aws".to_string(),
+            vec![
+                "registry.terraform.io/hashicorp/aws".to_string(),
+                "registry.terraform.io/example/aws".to_string()
+            ]
+        )],
+        module.provider_local_name_collisions()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_content_hash() -> result::Result<(), Box<dyn Error>> {
+    let first = r#"terraform {
+        required_version = ">= 1.0"
+        required_providers {
+            aws = { source = "hashicorp/aws", version = "~> 5.0" }
+        }
+    }
+
+    variable "region" {}"#;
+    let second = r#"resource "aws_instance" "web" {}
+
+    output "id" { value = aws_instance.web.id }"#;
+
+    let tmp_dir = TempDir::new("test")?;
+    fs::write(tmp_dir.path().join("a.tf"), first)?;
+    fs::write(tmp_dir.path().join("b.tf"), second)?;
+    let module = tfconfig::load_module(tmp_dir.path(), true)?;
+
+    let swapped_dir = TempDir::new("test")?;
+    fs::write(swapped_dir.path().join("a.tf"), second)?;
+    fs::write(swapped_dir.path().join("b.tf"), first)?;
+    let swapped = tfconfig::load_module(swapped_dir.path(), true)?;
+
+    assert_eq!(module.content_hash(), swapped.content_hash());
+
+    let mut changed = module.clone();
+    changed.variables.remove("region");
+    assert_ne!(module.content_hash(), changed.content_hash());
+
+    Ok(())
+}
+
+#[test]
+fn test_content_hash_covers_arguments() -> result::Result<(), Box<dyn Error>> {
+    let base = r#"terraform {
+        backend "s3" { bucket = "state" }
+    }
+
+    provider "aws" { region = "us-east-1" }
+
+    resource "aws_instance" "web" {
+        instance_type = "t3.micro"
+        count         = 1
+
+        ebs_block_device { volume_size = 10 }
+    }"#;
+    let hash = tfconfig::parse_str(base)?.content_hash();
+    assert_eq!(hash, tfconfig::parse_str(base)?.content_hash());
+
+    for (from, to) in [
+        (r#""state""#, r#""other-state""#),
+        (r#""us-east-1""#, r#""eu-west-1""#),
+        (r#""t3.micro""#, r#""t3.large""#),
+        ("count         = 1", "count         = 2"),
+        ("volume_size = 10", "volume_size = 20"),
+    ] {
+        let changed = tfconfig::parse_str(&base.replace(from, to))?;
+        assert_ne!(hash, changed.content_hash(), "{from} -> {to}");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_content_hash_covers_settings_and_state_blocks() -> result::Result<(), Box<dyn Error>> {
+    let base = r#"terraform {
+        experiments = [module_variable_optional_attrs]
+
+        cloud {
+            organization = "example"
+        }
+
+        encryption {
+            key_provider "pbkdf2" "main" {
+                passphrase = "correct-horse-battery-staple"
+            }
+
+            method "aes_gcm" "main" {
+                keys = key_provider.pbkdf2.main
+            }
+
+            state {
+                method = method.aes_gcm.main
+            }
+        }
+    }
+
+    variable "region" {
+        nullable  = true
+        ephemeral = false
+    }
+
+    moved {
+        from = aws_instance.old
+        to   = aws_instance.web
+    }
+
+    import {
+        to = aws_instance.web
+        id = "i-123"
+    }
+
+    removed {
+        from = aws_instance.legacy
+    }"#;
+    let hash = tfconfig::parse_str(base)?.content_hash();
+    assert_eq!(hash, tfconfig::parse_str(base)?.content_hash());
+
+    for (from, to) in [
+        ("nullable  = true", "nullable  = false"),
+        ("ephemeral = false", "ephemeral = true"),
+        ("module_variable_optional_attrs", "config_driven_move"),
+        (r#""example""#, r#""other""#),
+        (r#""correct-horse-battery-staple""#, r#""hunter2""#),
+        (
+            "keys = key_provider.pbkdf2.main",
+            "keys = key_provider.pbkdf2.other",
+        ),
+        (
+            "method = method.aes_gcm.main",
+            "method = method.aes_gcm.other",
+        ),
+        ("from = aws_instance.old", "from = aws_instance.older"),
+        (r#""i-123""#, r#""i-456""#),
+        ("from = aws_instance.legacy", "from = aws_instance.retired"),
+    ] {
+        let changed = tfconfig::parse_str(&base.replace(from, to))?;
+        assert_ne!(hash, changed.content_hash(), "{from} -> {to}");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_provider_version_array_of_joined_constraints() -> result::Result<(), Box<dyn Error>> {
+    let module = tfconfig::load_module_from_str(
+        Path::new("main.tf"),
+        r#"terraform {
+            required_providers {
+                aws = {
+                    source  = "hashicorp/aws"
+                    version = [">= 1.0, < 2.0"]
+                }
+                random = {
+                    source  = "hashicorp/random"
+                    version = [">= 3.0", "< 4.0, != 3.1.0"]
+                }
+            }
+        }"#,
+    )?;
+
+    assert_eq!(
+        vec![">= 1.0", "< 2.0"],
+        module.required_providers["aws"].version_constraints
+    );
+    assert_eq!(
+        vec![">= 3.0", "< 4.0", "!= 3.1.0"],
+        module.required_providers["random"].version_constraints
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_is_root_module() -> result::Result<(), Box<dyn Error>> {
+    let module = tfconfig::load_module_from_str(
+        Path::new("main.tf"),
+        r#"terraform {
+            backend "s3" {
+                bucket = "state"
+            }
+        }"#,
+    )?;
+    assert!(module.is_root_module());
+
+    let module = tfconfig::load_module_from_str(
+        Path::new("main.tf"),
+        r#"terraform {
+            cloud {
+                organization = "example"
+            }
+        }"#,
+    )?;
+    assert!(module.is_root_module());
+
+    let module = tfconfig::load_module_from_str(
+        Path::new("main.tf"),
+        r#"resource "aws_instance" "web" {}"#,
+    )?;
+    assert!(!module.is_root_module());
+
+    Ok(())
+}
+
+#[test]
+fn test_load_variable_values() -> result::Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new("test")?;
+    fs::write(
+        tmp_dir.path().join("main.tf"),
+        r#"variable "region" {}
+        variable "zones" {}
+        variable "size" {}"#,
+    )?;
+    fs::write(
+        tmp_dir.path().join("terraform.tfvars"),
+        r#"region = "us-east-1"
+        size = "small""#,
+    )?;
+    fs::write(tmp_dir.path().join("b.auto.tfvars"), r#"size = "large""#)?;
+    fs::write(
+        tmp_dir.path().join("a.auto.tfvars.json"),
+        r#"{"zones": ["a", "b"], "size": "medium"}"#,
+    )?;
+    fs::write(
+        tmp_dir.path().join("other.tfvars"),
+        r#"region = "eu-west-1""#,
+    )?;
+
+    let options = tfconfig::LoadOptions::new()
+        .strict(true)
+        .load_variable_values(true);
+    let module = tfconfig::load_module_with_options(tmp_dir.path(), &options)?;
+
+    assert_eq!(3, module.variable_values.len());
+    assert_eq!(
+        Some(&hcl::Expression::String("us-east-1".to_string())),
+        module.variable_values.get("region")
+    );
+    assert_eq!(
+        Some(&hcl::Expression::String("large".to_string())),
+        module.variable_values.get("size")
+    );
+    assert!(matches!(
+        module.variable_values.get("zones"),
+        Some(hcl::Expression::Array(zones)) if zones.len() == 2
+    ));
+
+    let module = tfconfig::load_module(tmp_dir.path(), true)?;
+    assert!(module.variable_values.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_unused_variables() -> result::Result<(), Box<dyn Error>> {
+    let module = tfconfig::load_module_from_str(
+        Path::new("main.tf"),
+        r#"variable "region" {}
+        variable "name" {}
+        variable "tags" {}
+        variable "unused" {
+            validation {
+                condition     = length(var.unused) > 0
+                error_message = "Must not be empty."
+            }
+        }
+
+        locals {
+            common_tags = var.tags
+        }
+
+        module "network" {
+            source = "./network"
+            region = var.region
+        }
+
+        resource "aws_instance" "web" {
+            ebs_block_device {
+                tags = { Name = "${var.name}-ebs" }
+            }
+        }"#,
+    )?;
+
+    assert_eq!(vec!["unused".to_string()], module.unused_variables());
+
+    Ok(())
+}
+
+#[test]
+fn test_provider_requirement_builder() {
+    let aliases = vec![
+        tfconfig::ProviderRef::new("aws".to_string(), "east".to_string()),
+        tfconfig::ProviderRef::new("aws".to_string(), "west".to_string()),
+    ];
+    let req = tfconfig::ProviderRequirement::default()
+        .with_source("hashicorp/aws".to_string())
+        .add_version_constraint(">= 4.0, < 6.0")
+        .add_version_constraint(">= 4.0")
+        .with_configuration_aliases(aliases.clone());
+
+    assert_eq!("hashicorp/aws", req.source);
+    assert_eq!(vec![">= 4.0", "< 6.0"], req.version_constraints);
+    assert_eq!(aliases, req.configuration_aliases);
+}
+
+#[test]
+fn test_allows_terraform_prerelease_version() -> result::Result<(), Box<dyn Error>> {
+    let module = tfconfig::load_module_from_str(
+        Path::new("main.tf"),
+        r#"terraform {
+            required_version = ">= 1.6.0-beta1"
+        }"#,
+    )?;
+
+    assert_eq!(vec![">= 1.6.0-beta1"], module.required_core);
+    assert!(module.allows_terraform_version("1.6.0")?);
+    assert!(module.allows_terraform_version("1.6.0-beta2")?);
+    assert!(module.allows_terraform_version("1.7.2")?);
+    assert!(!module.allows_terraform_version("1.5.0")?);
+    assert!(!module.allows_terraform_version("1.6.0-alpha1")?);
+    assert!(!module.allows_terraform_version("1.7.0-beta1")?);
+    assert!(module.allows_terraform_version("not a version").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_providers_iter_mut() -> result::Result<(), Box<dyn Error>> {
+    let mut module = tfconfig::load_module_from_str(
+        Path::new("main.tf"),
+        r#"terraform {
+            required_providers {
+                aws = { source = "HashiCorp/AWS" }
+                random = { source = "hashicorp/random", version = "~> 3.0" }
+            }
+        }"#,
+    )?;
+
+    assert_eq!(2, module.providers_iter_mut().len());
+    for (_, req) in module.providers_iter_mut() {
+        req.source = req.source.to_lowercase();
+        if req.version_constraints.is_empty() {
+            req.version_constraints.push(">= 1.0".to_string());
+        }
+    }
+
+    assert_eq!("hashicorp/aws", module.required_providers["aws"].source);
+    assert_eq!(
+        vec![">= 1.0"],
+        module.required_providers["aws"].version_constraints
+    );
+    assert_eq!(
+        vec!["~> 3.0"],
+        module.required_providers["random"].version_constraints
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_resource_ignore_changes() -> result::Result<(), Box<dyn Error>> {
+    let module = tfconfig::load_module_from_str(
+        Path::new("main.tf"),
+        r#"resource "aws_instance" "all" {
+            lifecycle {
+                ignore_changes = all
+            }
+        }
+
+        resource "aws_instance" "some" {
+            lifecycle {
+                create_before_destroy = true
+                ignore_changes        = [ami, tags["Name"]]
+            }
+        }
+
+        resource "aws_instance" "none" {}"#,
+    )?;
+
+    assert_eq!(
+        Some(tfconfig::IgnoreChanges::All),
+        module.managed_resources["aws_instance.all"].ignore_changes
+    );
+    assert_eq!(
+        Some(tfconfig::IgnoreChanges::Attributes(vec![
+            "ami".to_string(),
+            "tags[\"Name\"]".to_string()
+        ])),
+        module.managed_resources["aws_instance.some"].ignore_changes
+    );
+    assert_eq!(
+        None,
+        module.managed_resources["aws_instance.none"].ignore_changes
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_preprocess() -> result::Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new("test")?;
+    fs::write(
+        tmp_dir.path().join("main.tf"),
+        r#"variable "region" {
+            default = {{ REGION }}
+        }"#,
+    )?;
+
+    let module = tfconfig::load_module(tmp_dir.path(), false)?;
+    assert!(!module.variables.contains_key("region"));
+
+    let options = tfconfig::LoadOptions::new()
+        .strict(true)
+        .preprocess(Box::new(|file_name, contents| {
+            assert_eq!(Some("main.tf".as_ref()), file_name.file_name());
+            contents.replace("{{ REGION }}", r#""us-east-1""#)
+        }));
+    let module = tfconfig::load_module_with_options(tmp_dir.path(), &options)?;
+
+    assert!(module.variables.contains_key("region"));
+    assert_eq!(
+        Some(hcl::Expression::String("us-east-1".to_string())),
+        module.variables["region"].default
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_json_config_files_not_supported() -> result::Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new("test")?;
+    fs::write(
+        tmp_dir.path().join("main.tf"),
+        r#"variable "region" { description = "primary" }"#,
+    )?;
+    fs::write(
+        tmp_dir.path().join("override.tf.json"),
+        r#"{"variable": {"region": {"description": "override"}}}"#,
+    )?;
+    fs::write(
+        tmp_dir.path().join("versions.tf.json"),
+        r#"{"terraform": {"required_version": ">= 1.0"}}"#,
+    )?;
+
+    let module = tfconfig::load_module(tmp_dir.path(), true)?;
+
+    assert_eq!("primary", module.variables["region"].description);
+    assert!(module.required_core.is_empty());
+    assert_eq!(2, module.diagnostics.len());
+    assert!(module
+        .diagnostics
+        .iter()
+        .all(
+            |diagnostic| diagnostic.summary == "JSON syntax not supported"
+                && diagnostic.severity == DiagnosticSeverity::Error
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn test_partial_override_merges_arguments() -> result::Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new("test")?;
+    fs::write(
+        tmp_dir.path().join("main.tf"),
+        r#"variable "region" {
+            type    = string
+            default = "us-east-1"
+        }
+
+        output "id" {
+            value     = aws_instance.web.id
+            sensitive = true
+        }
+
+        resource "aws_instance" "web" {
+            ami           = "ami-123"
+            instance_type = "t3.micro"
+            depends_on    = [aws_vpc.main]
+        }
+
+        module "network" {
+            source  = "terraform-aws-modules/vpc/aws"
+            version = "5.0.0"
+        }"#,
+    )?;
+    fs::write(
+        tmp_dir.path().join("override.tf"),
+        r#"variable "region" {
+            description = "Region to deploy to"
+        }
+
+        output "id" {
+            description = "Instance ID"
+        }
+
+        resource "aws_instance" "web" {
+            instance_type = "t3.large"
+        }
+
+        module "network" {
+            version = "5.1.0"
+        }"#,
+    )?;
+
+    let module = tfconfig::load_module(tmp_dir.path(), true)?;
+
+    let region = &module.variables["region"];
+    assert_eq!("Region to deploy to", region.description);
+    assert!(region.type_.is_some());
+    assert!(region.default.is_some());
+    assert!(!region.is_required());
+
+    let output = &module.outputs["id"];
+    assert_eq!("Instance ID", output.description);
+    assert!(output.sensitive);
+    assert!(output.value.is_some());
+
+    let resource = &module.managed_resources["aws_instance.web"];
+    assert_eq!(
+        Some(&hcl::Expression::String("ami-123".to_string())),
+        resource.attributes.get("ami")
+    );
+    assert_eq!(
+        Some(&hcl::Expression::String("t3.large".to_string())),
+        resource.attributes.get("instance_type")
+    );
+    assert_eq!(vec!["aws_vpc.main"], resource.depends_on);
+
+    let network = &module.module_calls["network"];
+    assert_eq!("terraform-aws-modules/vpc/aws", network.source);
+    assert_eq!("5.1.0", network.version);
+
+    Ok(())
+}
+
+#[test]
+fn test_partial_override_merges_provider_arguments() -> result::Result<(), Box<dyn Error>> {
+    let tmp_dir = TempDir::new("test")?;
+    fs::write(
+        tmp_dir.path().join("main.tf"),
+        r#"provider "aws" {
+            region  = "us-east-1"
+            profile = "default"
+            version = "~> 5.0"
+
+            assume_role {
+                role_arn = "arn:aws:iam::123456789012:role/deploy"
+            }
+        }
+
+        provider "aws" {
+            alias  = "west"
+            region = "us-west-2"
+        }"#,
+    )?;
+    fs::write(
+        tmp_dir.path().join("override.tf"),
+        r#"provider "aws" {
+            region = "eu-west-1"
+        }
+
+        provider "aws" {
+            alias   = "west"
+            profile = "west"
+        }"#,
+    )?;
+
+    let module = tfconfig::load_module(tmp_dir.path(), true)?;
+    assert_eq!(2, module.provider_configs.len());
+
+    let aws = &module.provider_configs["aws"];
+    assert_eq!(
+        Some(&hcl::Expression::String("eu-west-1".to_string())),
+        aws.attributes.get("region")
+    );
+    assert_eq!(
+        Some(&hcl::Expression::String("default".to_string())),
+        aws.attributes.get("profile")
+    );
+    assert_eq!(Some("~> 5.0"), aws.version.as_deref());
+    assert_eq!(1, aws.nested_blocks.len());
+    assert_eq!("assume_role", aws.nested_blocks[0].0);
+
+    let west = &module.provider_configs["aws.west"];
+    assert_eq!(
+        Some(&hcl::Expression::String("us-west-2".to_string())),
+        west.attributes.get("region")
+    );
+    assert_eq!(
+        Some(&hcl::Expression::String("west".to_string())),
+        west.attributes.get("profile")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_json_override_files_loaded_last() -> result::Result<(), Box<dyn Error>> {
+    assert!(tfconfig::is_override_file(Path::new("override.tf.json")));
+    assert!(tfconfig::is_override_file(Path::new(
+        "network_override.tf.json"
+    )));
+
+    let tmp_dir = TempDir::new("test")?;
+    for file_name in [
+        "override.tf.json",
+        "main.tf.json",
+        "network_override.tf.json",
+        "versions.tf.json",
+    ] {
+        fs::write(tmp_dir.path().join(file_name), "{}")?;
+    }
+
+    let module = tfconfig::load_module(tmp_dir.path(), false)?;
+
+    // JSON files are skipped with one diagnostic each, in the order they are loaded.
+    let loaded: Vec<_> = module
+        .diagnostics
+        .iter()
+        .filter_map(|diagnostic| {
+            Path::new(diagnostic.detail.split_whitespace().next()?)
+                .file_name()?
+                .to_str()
+                .map(str::to_string)
+        })
+        .collect();
+    assert_eq!(4, loaded.len());
+
+    let mut primary = loaded[..2].to_vec();
+    primary.sort();
+    assert_eq!(vec!["main.tf.json", "versions.tf.json"], primary);
+
+    let mut overrides = loaded[2..].to_vec();
+    overrides.sort();
+    assert_eq!(
+        vec!["network_override.tf.json", "override.tf.json"],
+        overrides
+    );
+
+    Ok(())
+}